@@ -8,6 +8,7 @@
 #![deny(missing_docs)]
 #![doc = include_str!("../README.md")]
 
+mod router;
 mod string;
 mod template;
 mod value;
@@ -18,13 +19,61 @@ mod value;
 
 // Re-Exports
 
+#[cfg(feature = "serde")]
+pub use self::value::serializer::SerializeError;
 pub use self::{
+    router::{
+        Router,
+        RouterError,
+    },
     template::{
-        expand::ExpandError,
-        parse::ParseError,
+        build::{
+            BuildError,
+            TemplateBuilder,
+        },
+        expand::{
+            CompositePrefix,
+            ExpandError,
+            ExpandOptions,
+            Missing,
+        },
+        level::Level,
+        lint::{
+            Lint,
+            LintId,
+            Severity,
+        },
+        parse::{
+            ParseError,
+            ParseErrorKind,
+            ParseOptions,
+        },
+        Component,
+        Expression,
+        Literal,
+        Modifier,
+        OpLevel2,
+        OpLevel3,
+        Operator,
         Template,
+        Variable,
+        VariableName,
+        VariableSpecification,
     },
     value::{
+        convert::AsValueRef,
+        reference::{
+            Items,
+            Pairs,
+            ValueRef,
+        },
+        source::{
+            Layered,
+            SourceFn,
+            VariableSource,
+        },
+        Conflict,
+        MergeError,
         Value,
         Values,
     },
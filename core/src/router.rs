@@ -0,0 +1,410 @@
+use std::{
+    cmp::Reverse,
+    mem,
+};
+
+use thiserror::Error;
+
+use crate::{
+    template::{
+        expand::DEFAULT_BEHAVIOUR,
+        Component,
+        Operator,
+        Template,
+    },
+    value::Values,
+};
+
+// =============================================================================
+// Router
+// =============================================================================
+
+/// The [`Router`] type resolves URIs against a collection of [`Template`]
+/// instances, each associated with a payload of type `T`.
+///
+/// Resolution returns the payload of the most specific matching template, and
+/// the [`Values`] bound by matching.
+///
+/// Templates are ranked by specificity when added, such that:
+///
+/// - templates with a longer literal prefix are preferred
+/// - templates with fewer reserved or fragment expressions (`{+var}` and
+///   `{#var}`, which may match almost any input) are preferred
+/// - templates with fewer expressions are preferred
+/// - templates with more literal text in total are preferred
+///
+/// Templates which are otherwise equally specific are ranked by their literal
+/// structure (compared lexically), and templates with the same literal
+/// structure overlap and are rejected when added (see [`Router::add`]), so
+/// resolution never depends on the order in which templates were added.
+#[derive(Debug)]
+pub struct Router<'t, T> {
+    routes: Vec<Route<'t, T>>,
+}
+
+impl<'t, T> Router<'t, T> {
+    /// Adds a new [`Template`] and associated payload to the [`Router`].
+    ///
+    /// # Errors
+    ///
+    /// This function will fail if the template overlaps a template which has
+    /// already been added, leaving the router unchanged. Templates overlap
+    /// when they have the same literal structure (treating the leading
+    /// character of an operator, such as the `/` of `{/var}`, as part of the
+    /// literal structure), with compatible expressions at each position -
+    /// named expressions (`{;var}`, `{?var}`, and `{&var}`) are compatible
+    /// with each other, and all other expressions are compatible with each
+    /// other, whatever their variable names and modifiers.
+    ///
+    /// ```
+    /// # use uri_template_system_core::{ Router, Template };
+    /// #
+    /// let mut router = Router::default();
+    ///
+    /// router
+    ///     .add(Template::parse("/users/{id}").unwrap(), "user")
+    ///     .unwrap();
+    ///
+    /// assert!(router
+    ///     .add(Template::parse("/users/{name}").unwrap(), "named")
+    ///     .is_err());
+    /// assert!(router
+    ///     .add(Template::parse("/users{/id}").unwrap(), "path")
+    ///     .is_err());
+    /// ```
+    pub fn add(&mut self, template: Template<'t>, payload: T) -> Result<(), RouterError> {
+        let shape = Shape::of(&template);
+
+        if let Some(existing) = self.routes.iter().find(|route| route.shape == shape) {
+            return Err(RouterError::Ambiguous {
+                existing: existing.index,
+                index: self.routes.len(),
+            });
+        }
+
+        let specificity = Specificity::of(&template);
+        let position = self
+            .routes
+            .partition_point(|route| (&route.specificity, &route.shape) < (&specificity, &shape));
+
+        self.routes.insert(position, Route {
+            index: self.routes.len(),
+            payload,
+            shape,
+            specificity,
+            template,
+        });
+
+        Ok(())
+    }
+
+    /// Resolves the given URI to the most specific matching [`Template`],
+    /// returning the associated payload and the [`Values`] bound by matching
+    /// (see [`Template::match_uri`]), if any template matches.
+    ///
+    /// ```
+    /// # use uri_template_system_core::{ Router, Template, Value, Values };
+    /// #
+    /// let mut router = Router::default();
+    ///
+    /// router
+    ///     .add(Template::parse("/files{+path}").unwrap(), "file")
+    ///     .unwrap();
+    /// router
+    ///     .add(Template::parse("/files{/name}").unwrap(), "named")
+    ///     .unwrap();
+    ///
+    /// let (payload, values) = router.resolve("/files/readme").unwrap();
+    ///
+    /// assert_eq!(&"named", payload);
+    /// assert_eq!(Values::default().add("name", Value::item("readme")), values);
+    ///
+    /// let (payload, values) = router.resolve("/files/docs/readme").unwrap();
+    ///
+    /// assert_eq!(&"file", payload);
+    /// assert_eq!(
+    ///     Values::default().add("path", Value::item("/docs/readme")),
+    ///     values
+    /// );
+    /// ```
+    #[must_use]
+    pub fn resolve(&self, uri: &str) -> Option<(&T, Values)> {
+        self.routes.iter().find_map(|route| {
+            route
+                .template
+                .match_uri(uri)
+                .map(|values| (&route.payload, values))
+        })
+    }
+}
+
+impl<T> Default for Router<'_, T> {
+    fn default() -> Self {
+        Self { routes: Vec::new() }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Errors
+
+/// An [`Error`](std::error::Error) compatible type which may be the result of a
+/// failure of [`Router::add`], where the added template could not be routed
+/// unambiguously.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Error)]
+pub enum RouterError {
+    /// The template added overlaps an existing template (see [`Router::add`]),
+    /// so resolution between them would be ambiguous.
+    #[error("template {index} is ambiguous with existing template {existing}")]
+    Ambiguous {
+        /// The index (in order of addition) of the existing template.
+        existing: usize,
+        /// The index (in order of addition) which the ambiguous template would
+        /// have had.
+        index: usize,
+    },
+}
+
+// -----------------------------------------------------------------------------
+
+// Route
+
+#[derive(Debug)]
+struct Route<'t, T> {
+    index: usize,
+    payload: T,
+    shape: Vec<Shape>,
+    specificity: Specificity,
+    template: Template<'t>,
+}
+
+// -----------------------------------------------------------------------------
+
+// Specificity
+
+/// Specificity is ordered such that more specific templates are less than less
+/// specific templates (and so are sorted first).
+#[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
+struct Specificity {
+    prefix: Reverse<usize>,
+    reserved: usize,
+    expressions: usize,
+    literal: Reverse<usize>,
+}
+
+impl Specificity {
+    fn of(template: &Template<'_>) -> Self {
+        let prefix = match template.components().first() {
+            Some(Component::Literal(literal)) => literal.value().len(),
+            _ => 0,
+        };
+
+        let reserved = template
+            .expressions()
+            .filter(|expression| matches!(expression.operator(), Some(Operator::Level2(_))))
+            .count();
+
+        let literal = template
+            .components()
+            .iter()
+            .map(|component| match component {
+                Component::Literal(literal) => literal.value().len(),
+                Component::Expression(_) => 0,
+            })
+            .sum();
+
+        Self {
+            prefix: Reverse(prefix),
+            reserved,
+            expressions: template.expressions().count(),
+            literal: Reverse(literal),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Shape
+
+/// The shape of a template is the literal structure of the template, with the
+/// leading character of each operator treated as literal, and each expression
+/// reduced to whether it is named. Templates with equal shapes overlap, and
+/// otherwise equally specific templates are ordered by shape.
+#[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum Shape {
+    Literal(String),
+    Expression { named: bool },
+}
+
+impl Shape {
+    fn of(template: &Template<'_>) -> Vec<Self> {
+        let mut shape = Vec::new();
+        let mut literal = String::new();
+
+        for component in template.components() {
+            match component {
+                Component::Literal(value) => literal.push_str(value.value()),
+                Component::Expression(expression) => {
+                    let behaviour = expression
+                        .operator()
+                        .map_or(&DEFAULT_BEHAVIOUR, Operator::behaviour);
+
+                    if let Some(first) = behaviour.first {
+                        literal.push(first);
+                    }
+
+                    if !literal.is_empty() {
+                        shape.push(Self::Literal(mem::take(&mut literal)));
+                    }
+
+                    shape.push(Self::Expression {
+                        named: behaviour.named,
+                    });
+                }
+            }
+        }
+
+        if !literal.is_empty() {
+            shape.push(Self::Literal(literal));
+        }
+
+        shape
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Value;
+
+    fn router<'t>(templates: &[&'t str]) -> Router<'t, usize> {
+        let mut router = Router::default();
+
+        for (i, template) in templates.iter().enumerate() {
+            router.add(Template::parse(template).unwrap(), i).unwrap();
+        }
+
+        router
+    }
+
+    fn ambiguous(router: &mut Router<'_, usize>, template: &str) -> Option<usize> {
+        match router.add(Template::parse(template).unwrap().into_owned(), 99) {
+            Err(RouterError::Ambiguous { existing, .. }) => Some(existing),
+            Ok(()) => None,
+        }
+    }
+
+    #[test]
+    fn literal_prefix_ordering() {
+        let router = router(&["/{a}", "/users/{id}", "/users/admin"]);
+
+        assert_eq!(Some(&2), router.resolve("/users/admin").map(|(i, _)| i));
+        assert_eq!(Some(&1), router.resolve("/users/bob").map(|(i, _)| i));
+        assert_eq!(Some(&0), router.resolve("/bob").map(|(i, _)| i));
+    }
+
+    #[test]
+    fn operator_ordering() {
+        let router = router(&["{+rest}", "{/segment}"]);
+
+        assert_eq!(
+            Some((&1, Values::default().add("segment", Value::item("a")))),
+            router.resolve("/a")
+        );
+        assert_eq!(
+            Some((&0, Values::default().add("rest", Value::item("/a/b")))),
+            router.resolve("/a/b")
+        );
+    }
+
+    #[test]
+    fn expression_count_ordering() {
+        let router = router(&["/users/{id}{?q}", "/users/{id}"]);
+
+        assert_eq!(Some(&1), router.resolve("/users/1").map(|(i, _)| i));
+        assert_eq!(Some(&0), router.resolve("/users/1?q=a").map(|(i, _)| i));
+    }
+
+    #[test]
+    fn literal_length_ordering() {
+        for templates in [["/{a}", "/{b}.json"], ["/{b}.json", "/{a}"]] {
+            let router = router(&templates);
+
+            assert_eq!(
+                Some((&"/{b}.json", Values::default().add("b", Value::item("x")))),
+                router
+                    .resolve("/x.json")
+                    .map(|(i, values)| (&templates[*i], values))
+            );
+        }
+    }
+
+    #[test]
+    fn shape_ordering() {
+        for templates in [["{x}/a{y}b", "{x}/ab{y}"], ["{x}/ab{y}", "{x}/a{y}b"]] {
+            let router = router(&templates);
+
+            assert_eq!(
+                Some(&"{x}/a{y}b"),
+                router.resolve("/ab").map(|(i, _)| &templates[*i])
+            );
+        }
+    }
+
+    #[test]
+    fn ambiguous_rejection() {
+        let mut router = router(&["/users/{id}", "/users/{id}/posts"]);
+
+        assert!(matches!(
+            router.add(Template::parse("/users/{name}").unwrap(), 2),
+            Err(RouterError::Ambiguous {
+                existing: 0,
+                index: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn overlapping_operator_rejection() {
+        let mut router = router(&["/users/{id}", "/search{?q}"]);
+
+        assert_eq!(Some(0), ambiguous(&mut router, "/users/{+id}"));
+        assert_eq!(Some(0), ambiguous(&mut router, "/users/{id*}"));
+        assert_eq!(Some(1), ambiguous(&mut router, "/search{?query}"));
+        assert_eq!(None, ambiguous(&mut router, "/search?{q}"));
+        assert_eq!(None, ambiguous(&mut router, "/search{&q}"));
+        assert_eq!(Some(3), ambiguous(&mut router, "/search{&page}"));
+    }
+
+    #[test]
+    fn overlapping_prefix_rejection() {
+        let mut router = router(&["/a{/x}", "/b/{y}"]);
+
+        assert_eq!(Some(0), ambiguous(&mut router, "/a/{y}"));
+        assert_eq!(Some(1), ambiguous(&mut router, "/b{/x}"));
+        assert_eq!(Some(1), ambiguous(&mut router, "/b{/x*}"));
+        assert_eq!(None, ambiguous(&mut router, "/c{/x}"));
+    }
+
+    #[test]
+    fn rejection_preserves_routes() {
+        let mut router = router(&["/users/{id}"]);
+
+        assert!(router
+            .add(Template::parse("/users/{+id}").unwrap(), 1)
+            .is_err());
+        assert_eq!(Some(&0), router.resolve("/users/bob").map(|(i, _)| i));
+    }
+
+    #[test]
+    fn unmatched_resolution() {
+        let router = router(&["/users/{id}"]);
+
+        assert!(router.resolve("/posts/1").is_none());
+    }
+}
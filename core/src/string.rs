@@ -1,3 +1,4 @@
+pub mod decode;
 pub mod encode;
 pub mod satisfy;
 
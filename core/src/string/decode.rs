@@ -0,0 +1,40 @@
+// =============================================================================
+// Decode
+// =============================================================================
+
+// Functions
+
+/// Decodes any percent-encoded triplets in the given input, returning the
+/// decoded [`String`] if the input was validly encoded (all `%` characters
+/// begin a triplet) and the decoded octets form valid UTF-8.
+pub fn decode(raw: &str) -> Option<String> {
+    let bytes = raw.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut position = 0;
+
+    while position < bytes.len() {
+        match &bytes[position..] {
+            [b'%', a, b, ..] => {
+                decoded.push(hex_value(*a)? << 4 | hex_value(*b)?);
+                position += 3;
+            }
+            [b'%', ..] => return None,
+            [b, ..] => {
+                decoded.push(*b);
+                position += 1;
+            }
+            [] => break,
+        }
+    }
+
+    String::from_utf8(decoded).ok()
+}
+
+const fn hex_value(b: u8) -> Option<u8> {
+    match b {
+        b'0'..=b'9' => Some(b - b'0'),
+        b'a'..=b'f' => Some(b - b'a' + 10),
+        b'A'..=b'F' => Some(b - b'A' + 10),
+        _ => None,
+    }
+}
@@ -31,8 +31,9 @@ pub fn unreserved_or_reserved() -> impl Satisfy {
 
 #[rustfmt::skip]
 #[allow(clippy::match_like_matches_macro)]
+#[allow(clippy::redundant_pub_crate)]
 #[inline]
-const fn is_unreserved_ascii(b: u8) -> bool {
+pub(crate) const fn is_unreserved_ascii(b: u8) -> bool {
     match b {
         | b'\x61'..=b'\x7a' // a..z
         | b'\x41'..=b'\x5a' // A..Z
@@ -130,7 +131,7 @@ impl Satisfy for PercentEncoded {
         let mut pos = 0;
 
         loop {
-            match input[pos..].as_bytes() {
+            match &input.as_bytes()[pos..] {
                 [b'%', a, b, ..] if a.is_ascii_hexdigit() && b.is_ascii_hexdigit() => pos += 3,
                 _ => break,
             }
@@ -245,7 +246,7 @@ where
                 break;
             }
 
-            if exhausted.1 {
+            if exhausted.0 {
                 match self.0.satisfy(&input[pos..]) {
                     n if n > 0 => {
                         pos += n;
@@ -292,3 +293,47 @@ where
         pos
     }
 }
+
+// -----------------------------------------------------------------------------
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::mpsc,
+        thread,
+        time::Duration,
+    };
+
+    use super::*;
+
+    // Satisfaction is run on a separate thread, as a composite which fails to
+    // retry its first satisfier after a later satisfier has matched never
+    // terminates (rather than returning an incorrect length).
+
+    fn satisfy(input: &'static str) -> Option<usize> {
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            let satisfier = (
+                Ascii::new(|b| b.is_ascii_alphabetic()),
+                PercentEncoded,
+                Unicode::new(|c| !c.is_ascii()),
+            );
+
+            sender.send(satisfier.satisfy(input))
+        });
+
+        receiver.recv_timeout(Duration::from_secs(5)).ok()
+    }
+
+    #[test]
+    fn tuple_3_satisfaction() {
+        assert_eq!(Some(4), satisfy("%20a"));
+        assert_eq!(Some(5), satisfy("a%20b"));
+        assert_eq!(Some(7), satisfy("a\u{e9}%20b"));
+        assert_eq!(Some(3), satisfy("%20 a"));
+        assert_eq!(Some(1), satisfy("a-b"));
+    }
+}
@@ -1,18 +1,40 @@
+pub mod build;
+pub mod display;
 pub mod expand;
+pub mod level;
+pub mod lint;
+pub mod matches;
 pub mod parse;
+pub mod partial;
+#[cfg(feature = "serde")]
+pub mod serde;
+
+use std::{
+    borrow::Cow,
+    collections::HashSet,
+    ops::{
+        Range,
+        RangeInclusive,
+    },
+};
 
 use crate::{
     template::{
         expand::{
             Expand,
             ExpandError,
+            ExpandOptions,
         },
         parse::{
             ParseError,
+            ParseOptions,
             TryParse,
         },
     },
-    value::Values,
+    value::{
+        source::VariableSource,
+        Values,
+    },
 };
 
 // =============================================================================
@@ -21,14 +43,42 @@ use crate::{
 
 /// The [`Template`] type is the basis for most simple tasks. Parsing and
 /// expansion are both template functions.
-#[derive(Debug, Eq, PartialEq)]
+///
+/// A parsed [`Template`] borrows from the input it was parsed from where
+/// possible. An owned `Template<'static>` (which may be stored or sent across
+/// threads independently of the input) can be obtained using
+/// [`Template::into_owned`].
+///
+/// A [`Template`] (and each part of the parsed template) implements
+/// [`Display`](std::fmt::Display), rendering the template in RFC 6570 syntax,
+/// such that parsing the rendered output produces an equal template. The
+/// alternate form (`{:#}`) renders a normalized template, in which
+/// percent-encoded triplets in literals use uppercase hexadecimal digits
+/// (variable names are rendered unchanged).
+///
+/// Equality of templates (and of each part of the parsed template) is
+/// structural - spans are not compared, so templates with the same components
+/// are equal wherever the components occur within the input, and whether the
+/// template was parsed or built (see
+/// [`TemplateBuilder`](crate::TemplateBuilder)).
+///
+/// ```
+/// # use uri_template_system_core::Template;
+/// #
+/// let template = Template::parse("/a%2fb/{?q,page:3}").unwrap();
+///
+/// assert_eq!("/a%2fb/{?q,page:3}", template.to_string());
+/// assert_eq!("/a%2Fb/{?q,page:3}", format!("{template:#}"));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Template<'t> {
     components: Vec<Component<'t>>,
 }
 
 impl<'t> Template<'t> {
-    /// Expands the template using the given [`Values`], returning a [`String`]
-    /// if expansion was successful.
+    /// Expands the template using the given values (any [`VariableSource`],
+    /// such as [`Values`]), returning a [`String`] if expansion
+    /// was successful.
     ///
     /// # Errors
     ///
@@ -43,14 +93,184 @@ impl<'t> Template<'t> {
     /// let values = Values::default().add("name", Value::item("world"));
     ///
     /// assert_eq!("hello/world!", template.expand(&values).unwrap());
-    pub fn expand(&self, values: &Values) -> Result<String, ExpandError> {
+    pub fn expand(&self, values: &impl VariableSource) -> Result<String, ExpandError> {
+        self.expand_with(values, &ExpandOptions::default())
+    }
+
+    /// Expands the template using the given values and [`ExpandOptions`],
+    /// returning a [`String`] if expansion was successful.
+    ///
+    /// # Errors
+    ///
+    /// In addition to the failures possible for [`Template::expand`], this
+    /// function may fail where the given options require it, for example when
+    /// a variable is missing and [`Missing::Error`](expand::Missing::Error) is
+    /// set, or a prefix modifier is applied to a variable with a composite
+    /// value and [`CompositePrefix::Error`](expand::CompositePrefix::Error) is
+    /// set (see [`ExpandError::CompositePrefix`]).
+    ///
+    /// ```
+    /// # use uri_template_system_core::{ ExpandError, ExpandOptions, Missing, Template, Values, Value };
+    /// #
+    /// let template = Template::parse("/users/{userid}").unwrap();
+    /// let values = Values::default().add("user_id", Value::item("1"));
+    /// let options = ExpandOptions::default().missing(Missing::Error);
+    ///
+    /// assert!(matches!(
+    ///     template.expand_with(&values, &options),
+    ///     Err(ExpandError::Missing { name, span }) if name == "userid" && span == (7..15)
+    /// ));
+    /// ```
+    pub fn expand_with(
+        &self,
+        values: &impl VariableSource,
+        options: &ExpandOptions,
+    ) -> Result<String, ExpandError> {
         let mut expanded = String::default();
 
-        Expand::expand(self, values, &mut expanded)?;
+        Expand::expand(self, values, options, &mut expanded)?;
 
         Ok(expanded)
     }
 
+    /// Partially expands the template using the given values, returning a
+    /// new [`Template`] in which the variables present in the values have been
+    /// expanded, and expressions referencing other variables remain. Expansion
+    /// of the returned template with the remaining values is equivalent to
+    /// expansion of the original template with all values.
+    ///
+    /// Where an expression references both present and absent variables, the
+    /// present variables are expanded and the expression is rewritten to
+    /// continue the expansion (so `{?a,b}` with only `a` present becomes
+    /// `?a=1{&b}`).
+    ///
+    /// # Errors
+    ///
+    /// This function will fail (with [`ExpandError::Unrepresentable`]) where
+    /// the partial expansion of an expression cannot be represented - where a
+    /// defined variable follows an absent variable in the same expression, or
+    /// where a simple, reserved, or fragment expression (which have no
+    /// continuation form) is partially expanded.
+    ///
+    /// ```
+    /// # use uri_template_system_core::{ Template, Values, Value };
+    /// #
+    /// let template = Template::parse("https://{host}/search{?q,page}").unwrap();
+    /// let values = Values::default()
+    ///     .add("host", Value::item("example.com"))
+    ///     .add("q", Value::item("rust"));
+    ///
+    /// let partial = template.expand_partial(&values).unwrap();
+    ///
+    /// assert_eq!(
+    ///     "https://example.com/search?q=rust{&page}",
+    ///     partial.to_string()
+    /// );
+    ///
+    /// let values = Values::default().add("page", Value::item("2"));
+    ///
+    /// assert_eq!(
+    ///     "https://example.com/search?q=rust&page=2",
+    ///     partial.expand(&values).unwrap()
+    /// );
+    /// ```
+    pub fn expand_partial(
+        &self,
+        values: &impl VariableSource,
+    ) -> Result<Template<'static>, ExpandError> {
+        partial::expand_partial(&self.components, values)
+    }
+
+    /// Matches the given URI (or related form) against the template, returning
+    /// the [`Values`] which were bound during matching if the URI matches.
+    /// Bound values are percent-decoded, and variables which do not appear in
+    /// the URI (i.e. those which would have been undefined during expansion)
+    /// are not present in the returned [`Values`].
+    ///
+    /// Matching is the inverse of expansion, and is not always unambiguous
+    /// (for example, a non-exploded associative array expands identically to a
+    /// list). Where ambiguity exists, expressions are matched greedily, and the
+    /// simplest shape of [`Value`](crate::Value) is preferred - an item over a
+    /// list, and a list over an associative array.
+    ///
+    /// ```
+    /// # use uri_template_system_core::{ Template, Values, Value };
+    /// #
+    /// let template = Template::parse("/hello/{name}{?greeting}").unwrap();
+    /// let values = Values::default()
+    ///     .add("name", Value::item("world"))
+    ///     .add("greeting", Value::item("hi there"));
+    ///
+    /// assert_eq!(
+    ///     Some(values),
+    ///     template.match_uri("/hello/world?greeting=hi%20there")
+    /// );
+    /// assert_eq!(None, template.match_uri("/goodbye/world"));
+    /// ```
+    #[must_use]
+    pub fn match_uri(&self, uri: &str) -> Option<Values> {
+        let mut matched = Vec::new();
+
+        matches::match_components(&self.components, uri, &mut matched).then(|| {
+            matched
+                .into_iter()
+                .map(|(name, value, _)| (name, value))
+                .collect()
+        })
+    }
+
+    /// The variables referenced by the template as a [`Variable`] for each
+    /// occurrence, in the order in which they appear. Where a variable is
+    /// referenced more than once, each reference is included (see
+    /// [`Template::unique_variables`] for deduplicated variables).
+    ///
+    /// ```
+    /// # use uri_template_system_core::{ Modifier, OpLevel3, Operator, Template };
+    /// #
+    /// let template = Template::parse("/users/{id}{/id,name:3}").unwrap();
+    /// let variables = template.variables().collect::<Vec<_>>();
+    ///
+    /// assert_eq!(3, variables.len());
+    /// assert_eq!("id", variables[0].name().name());
+    /// assert_eq!(None, variables[0].operator());
+    /// assert_eq!(8..10, variables[0].span());
+    /// assert_eq!("id", variables[1].name().name());
+    /// assert_eq!(
+    ///     Some(Operator::Level3(OpLevel3::Path)),
+    ///     variables[1].operator()
+    /// );
+    /// assert_eq!(Some(Modifier::Prefix(3)), variables[2].modifier());
+    /// ```
+    pub fn variables(&self) -> impl Iterator<Item = Variable<'_>> {
+        self.expressions().flat_map(|expression| {
+            expression
+                .variables()
+                .iter()
+                .map(|variable| Variable::new(expression.operator(), variable))
+        })
+    }
+
+    /// The variables referenced by the template as a [`Variable`] for the first
+    /// occurrence of each variable name, in the order in which they appear.
+    ///
+    /// ```
+    /// # use uri_template_system_core::Template;
+    /// #
+    /// let template = Template::parse("/users/{id}{/id,name:3}").unwrap();
+    /// let names = template
+    ///     .unique_variables()
+    ///     .map(|variable| variable.name().name())
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(vec!["id", "name"], names);
+    /// ```
+    pub fn unique_variables(&self) -> impl Iterator<Item = Variable<'_>> {
+        let mut seen = HashSet::new();
+
+        self.variables()
+            .filter(move |variable| seen.insert(variable.name().name()))
+    }
+
     /// Parses a [`&str`] representing a potential template, and returns a new
     /// [`Template`] instance if valid. See [RFC6570](https://datatracker.ietf.org/doc/html/rfc6570)
     /// for the grammar of a valid URI Template. `uri-template-system` supports
@@ -73,6 +293,146 @@ impl<'t> Template<'t> {
         Self::try_parse(raw, 0).map(|(_, template)| template)
     }
 
+    /// Parses a [`&str`] representing a potential template, as for
+    /// [`Template::parse`], using the given [`ParseOptions`].
+    ///
+    /// # Errors
+    ///
+    /// In addition to the failures possible for [`Template::parse`], this
+    /// function may fail where the given options require it, for example when
+    /// an expression requires a higher [`Level`](level::Level) than the
+    /// maximum level set (see
+    /// [`ParseErrorKind::LevelExceeded`](parse::ParseErrorKind::LevelExceeded)).
+    ///
+    /// ```
+    /// # use uri_template_system_core::{ Level, ParseOptions, Template };
+    /// #
+    /// let options = ParseOptions::default().max_level(Level::Level1);
+    /// let err = Template::parse_with("/users/{id}{?q}", &options).unwrap_err();
+    ///
+    /// assert_eq!(11..15, err.span());
+    /// ```
+    pub fn parse_with(raw: &'t str, options: &ParseOptions) -> Result<Self, ParseError> {
+        let template = Self::parse(raw)?;
+
+        options.check(&template)?;
+
+        Ok(template)
+    }
+
+    /// Parses a [`&str`] representing a potential template, as for
+    /// [`Template::parse`], but continues parsing after an error to find every
+    /// error in the input, rather than stopping at the first.
+    ///
+    /// After an error within an expression, parsing continues after the
+    /// closing brace of the expression (or at the next opening brace, where
+    /// the expression is not closed). After an invalid literal character,
+    /// parsing continues with the next character.
+    ///
+    /// # Errors
+    ///
+    /// This function will fail when the given input is not a valid URI
+    /// Template, returning a [`ParseError`] for each error found, in the order
+    /// in which they appear in the input.
+    ///
+    /// ```
+    /// # use uri_template_system_core::{ ParseError, ParseErrorKind, Template };
+    /// #
+    /// let errors = Template::parse_all("/{a b}/{c:0}/d e").unwrap_err();
+    /// let kinds = errors.iter().map(ParseError::kind).collect::<Vec<_>>();
+    /// let spans = errors.iter().map(ParseError::span).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         ParseErrorKind::InvalidVarnameChar,
+    ///         ParseErrorKind::InvalidPrefix,
+    ///         ParseErrorKind::InvalidLiteralChar,
+    ///     ],
+    ///     kinds
+    /// );
+    /// assert_eq!(vec![3..4, 10..11, 14..15], spans);
+    /// ```
+    pub fn parse_all(raw: &'t str) -> Result<Self, Vec<ParseError>> {
+        parse::parse_components(raw, 0, true).map(Self::new)
+    }
+
+    /// The [`Component`]s of the template, in the order in which they appear.
+    ///
+    /// ```
+    /// # use uri_template_system_core::{ Component, Template };
+    /// #
+    /// let template = Template::parse("/hello/{name}").unwrap();
+    /// let components = template.components();
+    ///
+    /// assert!(matches!(&components[0], Component::Literal(literal) if literal.value() == "/hello/"));
+    /// assert!(matches!(&components[1], Component::Expression(expression) if expression.span() == (7..13)));
+    /// ```
+    #[must_use]
+    pub fn components(&self) -> &[Component<'t>] {
+        &self.components
+    }
+
+    /// The [`Expression`]s of the template, in the order in which they
+    /// appear.
+    ///
+    /// ```
+    /// # use uri_template_system_core::{ Modifier, OpLevel3, Operator, Template };
+    /// #
+    /// let template = Template::parse("/hello/{name}{?greeting:3,rest*}").unwrap();
+    /// let expressions = template.expressions().collect::<Vec<_>>();
+    ///
+    /// assert_eq!(None, expressions[0].operator());
+    /// assert_eq!(
+    ///     Some(Operator::Level3(OpLevel3::Query)),
+    ///     expressions[1].operator()
+    /// );
+    ///
+    /// let variables = expressions[1].variables();
+    ///
+    /// assert_eq!("greeting", variables[0].name().name());
+    /// assert_eq!(15..23, variables[0].name().span());
+    /// assert_eq!(Some(Modifier::Prefix(3)), variables[0].modifier());
+    /// assert_eq!(Some(Modifier::Explode), variables[1].modifier());
+    /// ```
+    pub fn expressions(&self) -> impl Iterator<Item = &Expression<'t>> {
+        self.components
+            .iter()
+            .filter_map(|component| match component {
+                Component::Expression(expression) => Some(expression),
+                Component::Literal(_) => None,
+            })
+    }
+
+    /// Converts the template into an owned `Template<'static>`, which no
+    /// longer borrows from the input it was parsed from. Parsing and expansion
+    /// behaviour is unchanged.
+    ///
+    /// ```
+    /// # use uri_template_system_core::{ Template, Values, Value };
+    /// #
+    /// fn parse_owned(raw: String) -> Template<'static> {
+    ///     Template::parse(&raw).unwrap().into_owned()
+    /// }
+    ///
+    /// let template = parse_owned(String::from("hello/{name}!"));
+    /// let values = Values::default().add("name", Value::item("world"));
+    ///
+    /// let expanded = std::thread::spawn(move || template.expand(&values).unwrap())
+    ///     .join()
+    ///     .unwrap();
+    ///
+    /// assert_eq!("hello/world!", expanded);
+    /// ```
+    #[must_use]
+    pub fn into_owned(self) -> Template<'static> {
+        Template::new(
+            self.components
+                .into_iter()
+                .map(Component::into_owned)
+                .collect(),
+        )
+    }
+
     const fn new(components: Vec<Component<'t>>) -> Self {
         Self { components }
     }
@@ -82,53 +442,151 @@ impl<'t> Template<'t> {
 
 // Component
 
-#[derive(Debug, Eq, PartialEq)]
+/// The [`Component`] type represents one of the two kinds of component which
+/// make up a [`Template`], as defined in
+/// [RFC6570 2](https://datatracker.ietf.org/doc/html/rfc6570#section-2).
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Component<'t> {
+    /// A [`Literal`] component, which is copied directly to the result of
+    /// expansion (percent-encoding characters where required).
     Literal(Literal<'t>),
+    /// An [`Expression`] component, which is replaced with the expansion of the
+    /// variables it references during expansion.
     Expression(Expression<'t>),
 }
 
+impl Component<'_> {
+    /// Converts the component into an owned `Component<'static>` (see
+    /// [`Template::into_owned`]).
+    #[must_use]
+    pub fn into_owned(self) -> Component<'static> {
+        match self {
+            Self::Literal(literal) => Component::Literal(literal.into_owned()),
+            Self::Expression(expression) => Component::Expression(expression.into_owned()),
+        }
+    }
+
+    /// The span (in bytes) of the component within the template input.
+    #[must_use]
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            Self::Literal(literal) => literal.span(),
+            Self::Expression(expression) => expression.span(),
+        }
+    }
+}
+
 // -----------------------------------------------------------------------------
 
 // Expression
 
-#[derive(Debug, Eq, PartialEq)]
+/// The [`Expression`] type represents an expression within a [`Template`], as
+/// defined in [RFC6570 2.2](https://datatracker.ietf.org/doc/html/rfc6570#section-2.2),
+/// consisting of an optional [`Operator`] and a list of variables.
+#[derive(Clone, Debug, Eq)]
 pub struct Expression<'t> {
-    pub operator: Option<Operator>,
-    pub variable_list: VariableList<'t>,
+    operator: Option<Operator>,
+    variable_list: VariableList<'t>,
+    span: Range<usize>,
 }
 
 impl<'t> Expression<'t> {
-    pub const fn new(operator: Option<Operator>, variable_list: VariableList<'t>) -> Self {
+    /// Converts the expression into an owned `Expression<'static>` (see
+    /// [`Template::into_owned`]).
+    #[must_use]
+    pub fn into_owned(self) -> Expression<'static> {
+        Expression::new(
+            self.operator,
+            self.variable_list
+                .into_iter()
+                .map(VariableSpecification::into_owned)
+                .collect(),
+            self.span,
+        )
+    }
+
+    /// The [`Operator`] of the expression, if any.
+    #[must_use]
+    pub const fn operator(&self) -> Option<Operator> {
+        self.operator
+    }
+
+    /// The span (in bytes) of the expression within the template input,
+    /// including the enclosing braces.
+    #[must_use]
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// The variables referenced by the expression, in the order in which they
+    /// appear.
+    #[must_use]
+    pub fn variables(&self) -> &[VariableSpecification<'t>] {
+        &self.variable_list
+    }
+
+    pub(crate) const fn new(
+        operator: Option<Operator>,
+        variable_list: VariableList<'t>,
+        span: Range<usize>,
+    ) -> Self {
         Self {
             operator,
             variable_list,
+            span,
         }
     }
 }
 
+impl PartialEq for Expression<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.operator == other.operator && self.variable_list == other.variable_list
+    }
+}
+
 // -----------------------------------------------------------------------------
 
 // Operator
 
-#[derive(Debug, Eq, PartialEq)]
+/// The [`Operator`] type represents the operator of an [`Expression`], as
+/// defined in [RFC6570 2.2](https://datatracker.ietf.org/doc/html/rfc6570#section-2.2).
+/// Operators are grouped by the template level at which they are introduced.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Operator {
+    /// An operator introduced at Level 2.
     Level2(OpLevel2),
+    /// An operator introduced at Level 3.
     Level3(OpLevel3),
 }
 
-#[derive(Debug, Eq, PartialEq)]
+/// The [`OpLevel2`] type represents the operators introduced at Level 2.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum OpLevel2 {
+    /// Fragment expansion (`{#var}`), see
+    /// [RFC6570 3.2.4](https://datatracker.ietf.org/doc/html/rfc6570#section-3.2.4).
     Fragment,
+    /// Reserved expansion (`{+var}`), see
+    /// [RFC6570 3.2.3](https://datatracker.ietf.org/doc/html/rfc6570#section-3.2.3).
     Reserved,
 }
 
-#[derive(Debug, Eq, PartialEq)]
+/// The [`OpLevel3`] type represents the operators introduced at Level 3.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum OpLevel3 {
+    /// Label expansion with dot-prefix (`{.var}`), see
+    /// [RFC6570 3.2.5](https://datatracker.ietf.org/doc/html/rfc6570#section-3.2.5).
     Label,
+    /// Path segment expansion (`{/var}`), see
+    /// [RFC6570 3.2.6](https://datatracker.ietf.org/doc/html/rfc6570#section-3.2.6).
     Path,
+    /// Path-style parameter expansion (`{;var}`), see
+    /// [RFC6570 3.2.7](https://datatracker.ietf.org/doc/html/rfc6570#section-3.2.7).
     PathParameter,
+    /// Form-style query expansion (`{?var}`), see
+    /// [RFC6570 3.2.8](https://datatracker.ietf.org/doc/html/rfc6570#section-3.2.8).
     Query,
+    /// Form-style query continuation (`{&var}`), see
+    /// [RFC6570 3.2.9](https://datatracker.ietf.org/doc/html/rfc6570#section-3.2.9).
     QueryContinuation,
 }
 
@@ -138,20 +596,124 @@ pub enum OpLevel3 {
 
 pub type VariableList<'t> = Vec<VariableSpecification<'t>>;
 
-pub type VariableSpecification<'t> = (VariableName<'t>, Option<Modifier>);
+/// The [`VariableSpecification`] type represents a reference to a variable
+/// within an [`Expression`], as defined in
+/// [RFC6570 2.3](https://datatracker.ietf.org/doc/html/rfc6570#section-2.3),
+/// consisting of a [`VariableName`] and an optional [`Modifier`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VariableSpecification<'t> {
+    name: VariableName<'t>,
+    modifier: Option<Modifier>,
+}
+
+impl<'t> VariableSpecification<'t> {
+    /// Converts the variable specification into an owned
+    /// `VariableSpecification<'static>` (see [`Template::into_owned`]).
+    #[must_use]
+    pub fn into_owned(self) -> VariableSpecification<'static> {
+        VariableSpecification::new(self.name.into_owned(), self.modifier)
+    }
+
+    /// The [`Modifier`] of the variable, if any.
+    #[must_use]
+    pub const fn modifier(&self) -> Option<Modifier> {
+        self.modifier
+    }
+
+    /// The [`VariableName`] of the variable.
+    #[must_use]
+    pub const fn name(&self) -> &VariableName<'t> {
+        &self.name
+    }
+
+    pub(crate) const fn new(name: VariableName<'t>, modifier: Option<Modifier>) -> Self {
+        Self { name, modifier }
+    }
+}
 
-#[derive(Debug, Eq, PartialEq)]
+/// The [`VariableName`] type represents the name of a variable, as given in the
+/// template input (percent-encoded triplets within variable names are not
+/// decoded).
+#[derive(Clone, Debug, Eq)]
 pub struct VariableName<'t> {
-    name: &'t str,
+    name: Cow<'t, str>,
+    span: Range<usize>,
 }
 
 impl<'t> VariableName<'t> {
-    pub const fn new(name: &'t str) -> Self {
-        Self { name }
+    /// Converts the variable name into an owned `VariableName<'static>` (see
+    /// [`Template::into_owned`]).
+    #[must_use]
+    pub fn into_owned(self) -> VariableName<'static> {
+        VariableName {
+            name: Cow::Owned(self.name.into_owned()),
+            span: self.span,
+        }
+    }
+
+    /// The name of the variable.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The span (in bytes) of the variable name within the template input.
+    #[must_use]
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    pub(crate) const fn new(name: &'t str, span: Range<usize>) -> Self {
+        Self {
+            name: Cow::Borrowed(name),
+            span,
+        }
     }
+}
 
-    pub const fn name(&self) -> &str {
-        self.name
+impl PartialEq for VariableName<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+/// The [`Variable`] type represents a single reference to a variable within a
+/// [`Template`], along with the context in which the variable is referenced
+/// (see [`Template::variables`]).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Variable<'a> {
+    operator: Option<Operator>,
+    variable: &'a VariableSpecification<'a>,
+}
+
+impl<'a> Variable<'a> {
+    /// The [`Modifier`] applied to the variable, if any.
+    #[must_use]
+    pub const fn modifier(&self) -> Option<Modifier> {
+        self.variable.modifier()
+    }
+
+    /// The [`VariableName`] of the variable.
+    #[must_use]
+    pub const fn name(&self) -> &'a VariableName<'a> {
+        self.variable.name()
+    }
+
+    /// The [`Operator`] of the [`Expression`] in which the variable is
+    /// referenced, if any.
+    #[must_use]
+    pub const fn operator(&self) -> Option<Operator> {
+        self.operator
+    }
+
+    /// The span (in bytes) of the variable name within the template input.
+    #[must_use]
+    pub fn span(&self) -> Range<usize> {
+        self.variable.name().span()
+    }
+
+    const fn new(operator: Option<Operator>, variable: &'a VariableSpecification<'a>) -> Self {
+        Self { operator, variable }
     }
 }
 
@@ -159,23 +721,94 @@ impl<'t> VariableName<'t> {
 
 // Modifier
 
-#[derive(Debug, Eq, PartialEq)]
+/// The [`Modifier`] type represents a value modifier applied to a variable, as
+/// defined in [RFC6570 2.4](https://datatracker.ietf.org/doc/html/rfc6570#section-2.4).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Modifier {
+    /// The explode modifier (`{var*}`), see
+    /// [RFC6570 2.4.2](https://datatracker.ietf.org/doc/html/rfc6570#section-2.4.2).
     Explode,
+    /// The prefix modifier (`{var:3}`) with the given maximum length (in
+    /// characters), see
+    /// [RFC6570 2.4.1](https://datatracker.ietf.org/doc/html/rfc6570#section-2.4.1).
     Prefix(usize),
 }
 
+/// The range of valid prefix modifier lengths, as defined in
+/// [RFC6570 2.4.1](https://datatracker.ietf.org/doc/html/rfc6570#section-2.4.1).
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) const PREFIX_RANGE: RangeInclusive<usize> = 1..=9999;
+
 // -----------------------------------------------------------------------------
 
 // Literal
 
-#[derive(Debug, Eq, PartialEq)]
+/// The [`Literal`] type represents literal text within a [`Template`], as
+/// defined in [RFC6570 2.1](https://datatracker.ietf.org/doc/html/rfc6570#section-2.1).
+#[derive(Clone, Debug, Eq)]
 pub struct Literal<'t> {
-    pub value: &'t str,
+    value: Cow<'t, str>,
+    span: Range<usize>,
 }
 
 impl<'t> Literal<'t> {
-    pub const fn new(value: &'t str) -> Self {
-        Self { value }
+    /// Converts the literal into an owned `Literal<'static>` (see
+    /// [`Template::into_owned`]).
+    #[must_use]
+    pub fn into_owned(self) -> Literal<'static> {
+        Literal {
+            value: Cow::Owned(self.value.into_owned()),
+            span: self.span,
+        }
+    }
+
+    /// The span (in bytes) of the literal within the template input.
+    #[must_use]
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// The literal text, as given in the template input.
+    #[must_use]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub(crate) const fn new(value: &'t str, span: Range<usize>) -> Self {
+        Self {
+            value: Cow::Borrowed(value),
+            span,
+        }
+    }
+}
+
+impl PartialEq for Literal<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn structural_equality() {
+        let a = Template::parse("/a{+b}{?c*}").unwrap();
+        let b = Template::parse("/a{+b}{?c*}").unwrap();
+        let offset = Template::parse("/x/a{+b}{?c*}").unwrap();
+
+        assert_eq!(a, b);
+        assert_eq!(a.expressions().nth(1), offset.expressions().nth(1));
+        assert_eq!(
+            a.variables().next().map(|variable| variable.name()),
+            offset.variables().next().map(|variable| variable.name())
+        );
+        assert_ne!(a.components()[0], offset.components()[0]);
+        assert_ne!(a, Template::parse("/a{+b}{?c}").unwrap());
     }
 }
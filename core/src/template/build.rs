@@ -0,0 +1,343 @@
+use std::borrow::Cow;
+
+use thiserror::Error;
+
+use crate::{
+    string::encode::Encode,
+    template::{
+        parse::{
+            self,
+            TryParse,
+        },
+        Component,
+        Expression,
+        Literal,
+        Modifier,
+        Operator,
+        Template,
+        VariableName,
+        VariableSpecification,
+        PREFIX_RANGE,
+    },
+};
+
+// =============================================================================
+// Build
+// =============================================================================
+
+/// The [`TemplateBuilder`] type constructs a [`Template`] programmatically,
+/// from literals and expressions, without the need to construct (and parse) a
+/// template string.
+///
+/// Literal values are percent-encoded where they contain characters which are
+/// not valid within a template literal (existing percent-encoded triplets are
+/// preserved), and variable names and modifiers are validated when the
+/// template is built.
+///
+/// ```
+/// # use uri_template_system_core::{ Modifier, OpLevel3, Operator, TemplateBuilder, Value, Values };
+/// #
+/// let template = TemplateBuilder::default()
+///     .literal("/users/")
+///     .expression(None, [("id", None)])
+///     .expression(Some(Operator::Level3(OpLevel3::Query)), [
+///         ("page", None),
+///         ("fields", Some(Modifier::Explode)),
+///     ])
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!("/users/{id}{?page,fields*}", template.to_string());
+///
+/// let values = Values::default()
+///     .add("id", Value::item("1"))
+///     .add("page", Value::item("2"));
+///
+/// assert_eq!("/users/1?page=2", template.expand(&values).unwrap());
+/// ```
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug, Default)]
+pub struct TemplateBuilder {
+    parts: Vec<Part>,
+}
+
+impl TemplateBuilder {
+    /// Appends a literal to the template. Characters which are not valid
+    /// within a template literal (including spaces, and the `{` and `}`
+    /// characters) are percent-encoded. Each literal is encoded independently
+    /// (so a percent-encoded triplet is only preserved where it is given within
+    /// a single literal) and consecutive literals are then combined.
+    #[must_use]
+    pub fn literal(mut self, value: impl AsRef<str>) -> Self {
+        let mut encoded = String::with_capacity(value.as_ref().len());
+
+        // Writing to a String is infallible.
+
+        let _ = encoded.encode(value.as_ref(), &parse::literal_satisfier());
+
+        match self.parts.last_mut() {
+            Some(Part::Literal(literal)) => literal.push_str(&encoded),
+            _ => self.parts.push(Part::Literal(encoded)),
+        }
+
+        self
+    }
+
+    /// Appends an expression to the template, with the given [`Operator`] (if
+    /// any) and variables, each given as a name and an optional [`Modifier`].
+    #[must_use]
+    pub fn expression<N>(
+        mut self,
+        operator: Option<Operator>,
+        variables: impl IntoIterator<Item = (N, Option<Modifier>)>,
+    ) -> Self
+    where
+        N: Into<String>,
+    {
+        self.parts.push(Part::Expression(
+            operator,
+            variables
+                .into_iter()
+                .map(|(name, modifier)| (name.into(), modifier))
+                .collect(),
+        ));
+
+        self
+    }
+
+    /// Builds the [`Template`], validating the expressions given. Spans within
+    /// the built template refer to positions within the equivalent template
+    /// string (as produced by [`Display`](std::fmt::Display)).
+    ///
+    /// # Errors
+    ///
+    /// This function will fail if an expression has no variables, a variable
+    /// name is not valid according to the RFC-defined grammar, or a prefix
+    /// modifier is outside the valid range (1 to 9999).
+    ///
+    /// ```
+    /// # use uri_template_system_core::{ BuildError, Modifier, TemplateBuilder };
+    /// #
+    /// let template = TemplateBuilder::default()
+    ///     .expression(None, [("name", Some(Modifier::Prefix(10000)))])
+    ///     .build();
+    ///
+    /// assert!(matches!(template, Err(BuildError::PrefixOutOfRange { .. })));
+    /// ```
+    pub fn build(self) -> Result<Template<'static>, BuildError> {
+        let mut components = Vec::with_capacity(self.parts.len());
+        let mut expressions = 0;
+        let mut position = 0;
+
+        for part in self.parts {
+            let component = match part {
+                Part::Literal(value) if value.is_empty() => continue,
+                Part::Literal(value) => {
+                    let literal = build_literal(value, position);
+
+                    position = literal.span.end;
+                    Component::Literal(literal)
+                }
+                Part::Expression(operator, variables) => {
+                    let expression = build_expression(expressions, operator, variables, position)?;
+
+                    expressions += 1;
+                    position = expression.span.end;
+                    Component::Expression(expression)
+                }
+            };
+
+            components.push(component);
+        }
+
+        Ok(Template::new(components))
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Part {
+    Expression(Option<Operator>, Vec<(String, Option<Modifier>)>),
+    Literal(String),
+}
+
+fn build_literal(encoded: String, position: usize) -> Literal<'static> {
+    Literal {
+        span: position..position + encoded.len(),
+        value: Cow::Owned(encoded),
+    }
+}
+
+fn build_expression(
+    index: usize,
+    operator: Option<Operator>,
+    variables: Vec<(String, Option<Modifier>)>,
+    position: usize,
+) -> Result<Expression<'static>, BuildError> {
+    if variables.is_empty() {
+        return Err(BuildError::EmptyExpression { index });
+    }
+
+    let start = position;
+    let mut position = position + 1 + usize::from(operator.is_some());
+    let mut variable_list = Vec::with_capacity(variables.len());
+
+    for (i, (name, modifier)) in variables.into_iter().enumerate() {
+        if !is_variable_name(&name) {
+            return Err(BuildError::InvalidVariableName { name });
+        }
+
+        if let Some(Modifier::Prefix(length)) = modifier {
+            if !PREFIX_RANGE.contains(&length) {
+                return Err(BuildError::PrefixOutOfRange { name, length });
+            }
+        }
+
+        position += usize::from(i > 0);
+
+        let span = position..position + name.len();
+
+        position = span.end + modifier.map_or(0, |modifier| modifier.to_string().len());
+        variable_list.push(VariableSpecification::new(
+            VariableName {
+                name: Cow::Owned(name),
+                span,
+            },
+            modifier,
+        ));
+    }
+
+    Ok(Expression::new(
+        operator,
+        variable_list,
+        start..position + 1,
+    ))
+}
+
+fn is_variable_name(name: &str) -> bool {
+    VariableName::try_parse(name, 0).map_or(false, |(position, _)| position == name.len())
+}
+
+// -----------------------------------------------------------------------------
+
+// Errors
+
+/// An [`Error`](std::error::Error) compatible type which may be the result of a
+/// failure of [`TemplateBuilder::build`], where the template described is not
+/// valid.
+#[derive(Debug, Error)]
+pub enum BuildError {
+    /// An expression was given without any variables, which is not valid
+    /// according to the URI Template grammar.
+    #[error("expression {index} has no variables")]
+    EmptyExpression {
+        /// The index of the expression, in order of addition to the builder
+        /// (counting expressions only).
+        index: usize,
+    },
+    /// A variable name was given which is not valid according to the URI
+    /// Template grammar (see
+    /// [RFC6570 2.3](https://datatracker.ietf.org/doc/html/rfc6570#section-2.3)).
+    #[error("invalid variable name: \"{name}\"")]
+    InvalidVariableName {
+        /// The invalid variable name.
+        name: String,
+    },
+    /// A prefix modifier was given with a length outside of the valid range (1
+    /// to 9999, see [RFC6570 2.4.1](https://datatracker.ietf.org/doc/html/rfc6570#section-2.4.1)).
+    #[error("prefix length {length} for variable \"{name}\" is out of range")]
+    PrefixOutOfRange {
+        /// The name of the variable with the invalid prefix modifier.
+        name: String,
+        /// The length given for the prefix modifier.
+        length: usize,
+    },
+}
+
+// -----------------------------------------------------------------------------
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::{
+        OpLevel2,
+        OpLevel3,
+    };
+
+    #[test]
+    fn literal_encoding() {
+        let template = TemplateBuilder::default()
+            .literal("/a b/")
+            .literal("{c}%2F%")
+            .build()
+            .unwrap();
+
+        assert_eq!("/a%20b/%7Bc%7D%2F%25", template.to_string());
+        assert_eq!(1, template.components().len());
+    }
+
+    #[test]
+    fn literal_independent_encoding() {
+        let template = TemplateBuilder::default()
+            .literal("%2")
+            .literal("F")
+            .build()
+            .unwrap();
+
+        assert_eq!("%252F", template.to_string());
+        assert_eq!(1, template.components().len());
+    }
+
+    #[test]
+    fn parsed_equivalence() {
+        let template = TemplateBuilder::default()
+            .literal("/base")
+            .expression(Some(Operator::Level3(OpLevel3::Path)), [(
+                "segments",
+                Some(Modifier::Explode),
+            )])
+            .expression(Some(Operator::Level2(OpLevel2::Fragment)), [
+                ("a.b", Some(Modifier::Prefix(12))),
+                ("c%2A", None),
+            ])
+            .literal("/")
+            .expression(None, [("x", None), ("y", None)])
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            Template::parse("/base{/segments*}{#a.b:12,c%2A}/{x,y}").unwrap(),
+            template
+        );
+    }
+
+    #[test]
+    fn invalid_expressions() {
+        assert!(matches!(
+            TemplateBuilder::default()
+                .literal("/")
+                .expression(None, Vec::<(String, _)>::new())
+                .build(),
+            Err(BuildError::EmptyExpression { index: 0 })
+        ));
+        assert!(matches!(
+            TemplateBuilder::default()
+                .expression(None, [("a b", None)])
+                .build(),
+            Err(BuildError::InvalidVariableName { .. })
+        ));
+        assert!(matches!(
+            TemplateBuilder::default()
+                .expression(None, [("a.", None)])
+                .build(),
+            Err(BuildError::InvalidVariableName { .. })
+        ));
+        assert!(matches!(
+            TemplateBuilder::default()
+                .expression(None, [("a", Some(Modifier::Prefix(0)))])
+                .build(),
+            Err(BuildError::PrefixOutOfRange { length: 0, .. })
+        ));
+    }
+}
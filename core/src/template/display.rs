@@ -0,0 +1,202 @@
+use std::fmt::{
+    Display,
+    Formatter,
+    Result,
+    Write,
+};
+
+use crate::template::{
+    Component,
+    Expression,
+    Literal,
+    Modifier,
+    OpLevel2,
+    OpLevel3,
+    Operator,
+    Template,
+    VariableName,
+    VariableSpecification,
+};
+
+// =============================================================================
+// Display
+// =============================================================================
+
+// Functions
+
+/// Writes the raw literal value, normalizing any percent-encoded triplets to
+/// use uppercase hexadecimal digits if the alternate flag (`{:#}`) is set.
+/// Variable names are never normalized, as names which differ only in the
+/// case of a percent-encoded triplet are distinct variables.
+fn write_raw(f: &mut Formatter<'_>, raw: &str) -> Result {
+    if !f.alternate() {
+        return f.write_str(raw);
+    }
+
+    let mut triplet = 0;
+
+    for c in raw.chars() {
+        match c {
+            '%' => {
+                triplet = 2;
+                f.write_char(c)?;
+            }
+            _ if triplet > 0 => {
+                triplet -= 1;
+                f.write_char(c.to_ascii_uppercase())?;
+            }
+            _ => f.write_char(c)?,
+        }
+    }
+
+    Ok(())
+}
+
+// =============================================================================
+// Display - Implementations
+// =============================================================================
+
+// Template
+
+impl Display for Template<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.components
+            .iter()
+            .try_for_each(|component| component.fmt(f))
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Component
+
+impl Display for Component<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Expression(expression) => expression.fmt(f),
+            Self::Literal(literal) => literal.fmt(f),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Expression
+
+impl Display for Expression<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_char('{')?;
+
+        if let Some(operator) = &self.operator {
+            operator.fmt(f)?;
+        }
+
+        for (i, variable) in self.variable_list.iter().enumerate() {
+            if i > 0 {
+                f.write_char(',')?;
+            }
+
+            variable.fmt(f)?;
+        }
+
+        f.write_char('}')
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Operator
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_char(match self {
+            Self::Level2(OpLevel2::Fragment) => '#',
+            Self::Level2(OpLevel2::Reserved) => '+',
+            Self::Level3(OpLevel3::Label) => '.',
+            Self::Level3(OpLevel3::Path) => '/',
+            Self::Level3(OpLevel3::PathParameter) => ';',
+            Self::Level3(OpLevel3::Query) => '?',
+            Self::Level3(OpLevel3::QueryContinuation) => '&',
+        })
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Variables
+
+impl Display for VariableSpecification<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.name.fmt(f)?;
+
+        if let Some(modifier) = &self.modifier {
+            modifier.fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for VariableName<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_str(&self.name)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Modifier
+
+impl Display for Modifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Explode => f.write_char('*'),
+            Self::Prefix(length) => write!(f, ":{length}"),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Literal
+
+impl Display for Literal<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write_raw(f, &self.value)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use crate::Template;
+
+    #[test]
+    fn template_display() {
+        let raw = "/a%2fb/{+base}{/path*}{?q,page:3}{&rest*}#{.x,y}";
+        let template = Template::parse(raw).unwrap();
+
+        assert_eq!(raw, template.to_string());
+    }
+
+    #[test]
+    fn normalized_template_display() {
+        let template = Template::parse("/a%2fb/{c%2a}").unwrap();
+
+        assert_eq!("/a%2Fb/{c%2a}", format!("{template:#}"));
+    }
+
+    #[test]
+    fn normalized_template_round_trip() {
+        let template = Template::parse("/a%2fb/{c%2a,d%2A}").unwrap();
+        let normalized = format!("{template:#}");
+
+        assert_eq!(
+            template.components()[1],
+            Template::parse(&normalized).unwrap().components()[1]
+        );
+    }
+}
@@ -1,6 +1,9 @@
-use std::fmt::{
-    Error,
-    Write,
+use std::{
+    fmt::{
+        Error,
+        Write,
+    },
+    ops::Range,
 };
 
 use thiserror::Error;
@@ -24,8 +27,8 @@ use crate::{
         Template,
     },
     value::{
-        Value,
-        Values,
+        reference::ValueRef,
+        source::VariableSource,
     },
 };
 
@@ -37,7 +40,12 @@ use crate::{
 
 #[allow(clippy::module_name_repetitions)]
 pub trait Expand {
-    fn expand(&self, values: &Values, write: &mut impl Write) -> Result<(), ExpandError>;
+    fn expand(
+        &self,
+        values: &impl VariableSource,
+        options: &ExpandOptions,
+        write: &mut impl Write,
+    ) -> Result<(), ExpandError>;
 }
 
 // -----------------------------------------------------------------------------
@@ -46,14 +54,128 @@ pub trait Expand {
 
 /// An [`Error`](std::error::Error) compatible type which may be the result of a
 /// failure of [`Template::expand`] (given a valid [`Template`] and provided
-/// [`Values`]).
+/// values).
 #[allow(clippy::module_name_repetitions)]
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum ExpandError {
     /// Formatting for this expansion failed due to an internal error in
     /// [`std::fmt::Write`], which is not recoverable.
     #[error("formatting failed")]
     Format(#[from] Error),
+    /// The partial expansion of an expression (see
+    /// [`Template::expand_partial`]) could not be represented as an expression
+    /// in the resulting template, as the expansion of the remaining variables
+    /// would depend on whether preceding variables are defined.
+    #[error("partial expansion of the expression at {span:?} cannot be represented")]
+    Unrepresentable {
+        /// The span (in bytes) of the expression within the template.
+        span: Range<usize>,
+    },
+    /// A prefix modifier was applied to a variable with a composite value (a
+    /// list or associative array), which is not permitted by
+    /// [RFC6570 2.4.1](https://datatracker.ietf.org/doc/html/rfc6570#section-2.4.1),
+    /// when expanding with [`CompositePrefix::Error`].
+    #[error(
+        "prefix modifier {modifier} applied to composite variable \"{name}\" in the expression at \
+         {span:?}"
+    )]
+    CompositePrefix {
+        /// The name of the variable with the composite value.
+        name: String,
+        /// The offending modifier.
+        modifier: Modifier,
+        /// The span (in bytes) of the expression referencing the variable
+        /// within the template.
+        span: Range<usize>,
+    },
+    /// A variable referenced by the template was missing from the values given,
+    /// or was undefined, when expanding with [`Missing::Error`].
+    #[error("variable \"{name}\" in the expression at {span:?} is missing or undefined")]
+    Missing {
+        /// The name of the missing variable.
+        name: String,
+        /// The span (in bytes) of the expression referencing the variable
+        /// within the template.
+        span: Range<usize>,
+    },
+}
+
+// -----------------------------------------------------------------------------
+
+// Options
+
+/// The [`ExpandOptions`] type configures the behaviour of expansion (see
+/// [`Template::expand_with`]).
+///
+/// The default options give expansion as defined by the RFC, except that
+/// prefix modifiers applied to composite values are ignored rather than
+/// treated as errors (see [`CompositePrefix`]).
+///
+/// ```
+/// # use uri_template_system_core::{ ExpandOptions, Missing, Template, Values, Value };
+/// #
+/// let template = Template::parse("/users/{userid}").unwrap();
+/// let values = Values::default().add("user_id", Value::item("1"));
+/// let options = ExpandOptions::default().missing(Missing::Error);
+///
+/// assert_eq!("/users/", template.expand(&values).unwrap());
+/// assert!(template.expand_with(&values, &options).is_err());
+/// ```
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug, Default)]
+pub struct ExpandOptions {
+    composite_prefix: CompositePrefix,
+    missing: Missing,
+}
+
+impl ExpandOptions {
+    /// Sets the behaviour of expansion when a prefix modifier is applied to a
+    /// variable with a composite value, and returns the modified options to
+    /// allow for chaining of calls during construction.
+    #[must_use]
+    pub const fn composite_prefix(mut self, composite_prefix: CompositePrefix) -> Self {
+        self.composite_prefix = composite_prefix;
+        self
+    }
+
+    /// Sets the behaviour of expansion when a variable referenced by the
+    /// template is missing from the values given, or is undefined, and returns
+    /// the modified options to allow for chaining of calls during
+    /// construction.
+    #[must_use]
+    pub const fn missing(mut self, missing: Missing) -> Self {
+        self.missing = missing;
+        self
+    }
+}
+
+/// The [`CompositePrefix`] type determines the behaviour of expansion when a
+/// prefix modifier is applied to a variable with a composite value (see
+/// [`ExpandOptions::composite_prefix`]).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum CompositePrefix {
+    /// Expansion fails with [`ExpandError::CompositePrefix`], as the RFC
+    /// defines this to be an error.
+    Error,
+    /// The prefix modifier is ignored, and the value is expanded as though no
+    /// modifier were given (the default).
+    #[default]
+    Ignore,
+}
+
+/// The [`Missing`] type determines the behaviour of expansion when a variable
+/// is missing or undefined (see [`ExpandOptions::missing`]).
+///
+/// Note that empty lists and associative arrays are considered undefined
+/// ([RFC6570 2.3](https://datatracker.ietf.org/doc/html/rfc6570#section-2.3)).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Missing {
+    /// Expansion fails with [`ExpandError::Missing`].
+    Error,
+    /// The variable is skipped, as defined by the RFC.
+    #[default]
+    Skip,
 }
 
 // =============================================================================
@@ -62,11 +184,16 @@ pub enum ExpandError {
 
 // Template
 
-impl<'t> Expand for Template<'t> {
-    fn expand(&self, values: &Values, write: &mut impl Write) -> Result<(), ExpandError> {
+impl Expand for Template<'_> {
+    fn expand(
+        &self,
+        values: &impl VariableSource,
+        options: &ExpandOptions,
+        write: &mut impl Write,
+    ) -> Result<(), ExpandError> {
         self.components
             .iter()
-            .try_for_each(|component| component.expand(values, write))
+            .try_for_each(|component| component.expand(values, options, write))
     }
 }
 
@@ -74,11 +201,16 @@ impl<'t> Expand for Template<'t> {
 
 // Component
 
-impl<'t> Expand for Component<'t> {
-    fn expand(&self, values: &Values, write: &mut impl Write) -> Result<(), ExpandError> {
+impl Expand for Component<'_> {
+    fn expand(
+        &self,
+        values: &impl VariableSource,
+        options: &ExpandOptions,
+        write: &mut impl Write,
+    ) -> Result<(), ExpandError> {
         match self {
-            Self::Expression(expression) => expression.expand(values, write),
-            Self::Literal(literal) => literal.expand(values, write),
+            Self::Expression(expression) => expression.expand(values, options, write),
+            Self::Literal(literal) => literal.expand(values, options, write),
         }
     }
 }
@@ -87,20 +219,26 @@ impl<'t> Expand for Component<'t> {
 
 // Expression
 
-impl<'t> Expand for Expression<'t> {
+impl Expand for Expression<'_> {
     #[allow(clippy::cognitive_complexity)] // TODO: Reduce?
     #[allow(clippy::equatable_if_let)]
     #[allow(clippy::too_many_lines)]
-    fn expand(&self, values: &Values, write: &mut impl Write) -> Result<(), ExpandError> {
+    fn expand(
+        &self,
+        values: &impl VariableSource,
+        options: &ExpandOptions,
+        write: &mut impl Write,
+    ) -> Result<(), ExpandError> {
         let behaviour = self
             .operator
-            .as_ref()
             .map_or(&DEFAULT_BEHAVIOUR, Operator::behaviour);
 
         let satisfier = behaviour.allow.satisfier();
         let mut first = true;
 
-        for (var_name, modifier) in &self.variable_list {
+        for variable in &self.variable_list {
+            let (var_name, modifier) = (variable.name(), variable.modifier());
+
             // Lookup the value for the scanned variable name, and then
             //
             // * If the varname is unknown or corresponds to a variable with an undefined
@@ -108,9 +246,32 @@ impl<'t> Expand for Expression<'t> {
 
             let value = match values.get(var_name.name()) {
                 Some(value) if value.defined() => value,
+                _ if options.missing == Missing::Error => {
+                    return Err(ExpandError::Missing {
+                        name: var_name.name().into(),
+                        span: self.span(),
+                    });
+                }
                 _ => continue,
             };
 
+            // Prefix modifiers are not applicable to variables that have composite values
+            // (Section 2.4.1), so this is an error unless leniency has been requested.
+
+            if let (
+                Some(modifier @ Modifier::Prefix(_)),
+                ValueRef::AssociativeArray(_) | ValueRef::List(_),
+            ) = (modifier, &value)
+            {
+                if options.composite_prefix == CompositePrefix::Error {
+                    return Err(ExpandError::CompositePrefix {
+                        name: var_name.name().into(),
+                        modifier,
+                        span: self.span(),
+                    });
+                }
+            }
+
             // * If this is the first defined variable for this expression, append the first
             //   string for this expression type to the result string and remember that it
             //   has been done.  Otherwise, append the sep string to the result string.
@@ -125,7 +286,7 @@ impl<'t> Expand for Expression<'t> {
                 write.write_char(behaviour.sep)?;
             }
 
-            if let Value::Item(value) = value {
+            if let ValueRef::Item(value) = &value {
                 // If this variable's value is a string, then
 
                 if behaviour.named {
@@ -157,7 +318,7 @@ impl<'t> Expand for Expression<'t> {
                         //   set, while taking care not to split multi-octet or pct-encoded triplet
                         //   characters that represent a single Unicode code point;
 
-                        let pos: usize = value.chars().take(*length).map(char::len_utf8).sum();
+                        let pos: usize = value.chars().take(length).map(char::len_utf8).sum();
 
                         write.encode(&value[..pos], &satisfier)?;
                     }
@@ -167,7 +328,7 @@ impl<'t> Expand for Expression<'t> {
 
                         write.encode(value, &satisfier)?;
                     }
-                };
+                }
             } else if let Some(Modifier::Explode) = modifier {
                 // else if an explode modifier is given, then
 
@@ -175,10 +336,10 @@ impl<'t> Expand for Expression<'t> {
                     // * if named is true, then for each defined list member or array (name, value)
                     //   pair with a defined value, do:
 
-                    if let Value::AssociativeArray(value) = value {
+                    if let ValueRef::AssociativeArray(value) = &value {
                         let mut first = true;
 
-                        for (name, value) in value {
+                        value.try_for_each(|name, value| {
                             // + if this is not the first defined member/value, append the sep
                             //   string to the result string;
 
@@ -206,11 +367,13 @@ impl<'t> Expand for Expression<'t> {
                                 write.write_char('=')?;
                                 write.encode(value, &satisfier)?;
                             }
-                        }
-                    } else if let Value::List(value) = value {
+
+                            Ok(())
+                        })?;
+                    } else if let ValueRef::List(value) = &value {
                         let mut first = true;
 
-                        for value in value {
+                        for value in value.iter() {
                             // + if this is not the first defined member/value, append the sep
                             //   string to the result string;
 
@@ -243,7 +406,7 @@ impl<'t> Expand for Expression<'t> {
                 } else {
                     // * else if named is false, then
 
-                    if let Value::AssociativeArray(value) = value {
+                    if let ValueRef::AssociativeArray(value) = &value {
                         // + if this is an array of (name, value) pairs, append each pair with a
                         //   defined value to the result string as "name=value", after pct-encoding
                         //   any characters that are not in the allow set, with the sep string
@@ -251,7 +414,7 @@ impl<'t> Expand for Expression<'t> {
 
                         let mut first = true;
 
-                        for (name, value) in value {
+                        value.try_for_each(|name, value| {
                             if !value.is_empty() {
                                 if first {
                                     first = false;
@@ -262,9 +425,9 @@ impl<'t> Expand for Expression<'t> {
 
                             write.encode(name, &satisfier)?;
                             write.write_char('=')?;
-                            write.encode(value, &satisfier)?;
-                        }
-                    } else if let Value::List(value) = value {
+                            write.encode(value, &satisfier)
+                        })?;
+                    } else if let ValueRef::List(value) = &value {
                         // + if this is a list, append each defined list member to the result
                         //   string, after pct-encoding any characters that are not in the allow
                         //   set, with the sep string appended to the result between each defined
@@ -272,7 +435,7 @@ impl<'t> Expand for Expression<'t> {
 
                         let mut first = true;
 
-                        for value in value {
+                        for value in value.iter() {
                             if !value.is_empty() {
                                 if first {
                                     first = false;
@@ -304,7 +467,7 @@ impl<'t> Expand for Expression<'t> {
                     write.write_char('=')?;
                 }
 
-                if let Value::AssociativeArray(value) = value {
+                if let ValueRef::AssociativeArray(value) = &value {
                     // * if this variable's value is an associative array or any other form of
                     //   paired (name, value) structure, append each pair with defined value to the
                     //   result string as "name,value", after pct-encoding any characters that are
@@ -313,7 +476,7 @@ impl<'t> Expand for Expression<'t> {
 
                     let mut first = true;
 
-                    for (name, value) in value {
+                    value.try_for_each(|name, value| {
                         if !value.is_empty() {
                             if first {
                                 first = false;
@@ -325,8 +488,10 @@ impl<'t> Expand for Expression<'t> {
                             write.write_char(',')?;
                             write.encode(value, &satisfier)?;
                         }
-                    }
-                } else if let Value::List(value) = value {
+
+                        Ok(())
+                    })?;
+                } else if let ValueRef::List(value) = &value {
                     // * if this variable's value is a list, append each defined list member to the
                     //   result string, after pct-encoding any characters that are not in the allow
                     //   set, with a comma (",") appended to the result between each defined list
@@ -334,7 +499,7 @@ impl<'t> Expand for Expression<'t> {
 
                     let mut first = true;
 
-                    for value in value {
+                    for value in value.iter() {
                         if !value.is_empty() {
                             if first {
                                 first = false;
@@ -353,8 +518,9 @@ impl<'t> Expand for Expression<'t> {
     }
 }
 
+#[allow(clippy::redundant_pub_crate)]
 #[derive(Debug)]
-struct Behaviour {
+pub(crate) struct Behaviour {
     pub first: Option<char>,
     pub sep: char,
     pub named: bool,
@@ -362,14 +528,15 @@ struct Behaviour {
     pub allow: Allow,
 }
 
+#[allow(clippy::redundant_pub_crate)]
 #[derive(Debug)]
-enum Allow {
+pub(crate) enum Allow {
     U,
     UR,
 }
 
 impl Allow {
-    fn satisfier(&self) -> Box<dyn Satisfy> {
+    pub fn satisfier(&self) -> Box<dyn Satisfy> {
         match self {
             Self::U => Box::new(satisfy::unreserved()),
             Self::UR => Box::new(satisfy::unreserved_or_reserved()),
@@ -382,7 +549,7 @@ impl Allow {
 // Operator
 
 impl Operator {
-    fn behaviour(&self) -> &Behaviour {
+    pub(crate) const fn behaviour(self) -> &'static Behaviour {
         match self {
             Self::Level2(op_level_2) => match op_level_2 {
                 OpLevel2::Fragment => &FRAGMENT_BEHAVIOUR,
@@ -399,7 +566,8 @@ impl Operator {
     }
 }
 
-static DEFAULT_BEHAVIOUR: Behaviour = Behaviour {
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) static DEFAULT_BEHAVIOUR: Behaviour = Behaviour {
     first: None,
     sep: ',',
     named: false,
@@ -467,10 +635,81 @@ static QUERY_CONTINUATION_BEHAVIOUR: Behaviour = Behaviour {
 
 // Literal
 
-impl<'t> Expand for Literal<'t> {
-    fn expand(&self, _values: &Values, write: &mut impl Write) -> Result<(), ExpandError> {
-        write.encode(self.value, &satisfy::unreserved_or_reserved())?;
+impl Expand for Literal<'_> {
+    fn expand(
+        &self,
+        _values: &impl VariableSource,
+        _options: &ExpandOptions,
+        write: &mut impl Write,
+    ) -> Result<(), ExpandError> {
+        write.encode(&self.value, &satisfy::unreserved_or_reserved())?;
 
         Ok(())
     }
 }
+
+// -----------------------------------------------------------------------------
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CompositePrefix,
+        ExpandError,
+        ExpandOptions,
+        Missing,
+        Modifier,
+        Template,
+        Value,
+        Values,
+    };
+
+    #[test]
+    fn missing_variable_expansion() {
+        let template = Template::parse("/{a}{?b,c}").unwrap();
+        let values = Values::default()
+            .add("a", Value::item("x"))
+            .add("b", Value::item("y"))
+            .add("c", Value::list(Vec::<String>::new()));
+
+        let skip = ExpandOptions::default();
+        let error = ExpandOptions::default().missing(Missing::Error);
+
+        assert_eq!("/x?b=y", template.expand_with(&values, &skip).unwrap());
+        assert!(matches!(
+            template.expand_with(&values, &error),
+            Err(ExpandError::Missing { name, span }) if name == "c" && span == (4..10)
+        ));
+        assert!(matches!(
+            template.expand_with(&Values::default(), &error),
+            Err(ExpandError::Missing { name, .. }) if name == "a"
+        ));
+    }
+
+    #[test]
+    fn composite_prefix_expansion() {
+        let template = Template::parse("{?list:3}").unwrap();
+        let values = Values::default().add("list", Value::list(["red", "green"]));
+
+        let ignore = ExpandOptions::default();
+        let error = ExpandOptions::default().composite_prefix(CompositePrefix::Error);
+
+        assert!(matches!(
+            template.expand_with(&values, &error),
+            Err(ExpandError::CompositePrefix {
+                name,
+                modifier: Modifier::Prefix(3),
+                span
+            }) if name == "list" && span == (0..9)
+        ));
+        assert_eq!(
+            "?list=red,green",
+            template.expand_with(&values, &ignore).unwrap()
+        );
+        assert_eq!(
+            template.expand(&values).unwrap(),
+            template.expand_with(&values, &ignore).unwrap()
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use std::fmt::{
+    self,
+    Display,
+    Formatter,
+};
+
+use crate::template::{
+    Expression,
+    Operator,
+    Template,
+};
+
+// =============================================================================
+// Level
+// =============================================================================
+
+/// The [`Level`] type describes the levels of template defined by the RFC (see
+/// [RFC6570 1.2](https://datatracker.ietf.org/doc/html/rfc6570#section-1.2)),
+/// where each level adds expression features to those of the previous level.
+///
+/// Levels are ordered, such that a template of a given level is supported by
+/// any implementation supporting that level or higher.
+///
+/// - Level 1 templates contain only simple expressions, with one variable per
+///   expression (`{var}`)
+/// - Level 2 templates may also contain reserved and fragment expressions
+///   (`{+var}` and `{#var}`)
+/// - Level 3 templates may also contain expressions with multiple variables
+///   (`{x,y}`), and label, path, path parameter, query, and query continuation
+///   expressions (`{.var}`, `{/var}`, `{;var}`, `{?var}`, and `{&var}`)
+/// - Level 4 templates may also contain variables with modifiers (`{var:3}` and
+///   `{var*}`)
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Level {
+    /// Level 1 - simple string expansion.
+    Level1,
+    /// Level 2 - reserved and fragment expansion.
+    Level2,
+    /// Level 3 - multiple variables, and the remaining operators.
+    Level3,
+    /// Level 4 - prefix and explode modifiers.
+    Level4,
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let level = match self {
+            Self::Level1 => 1,
+            Self::Level2 => 2,
+            Self::Level3 => 3,
+            Self::Level4 => 4,
+        };
+
+        write!(f, "Level {level}")
+    }
+}
+
+// =============================================================================
+// Level - Implementations
+// =============================================================================
+
+// Template
+
+impl Template<'_> {
+    /// The minimum [`Level`] of implementation required to support the
+    /// template, being the highest level of any expression within the
+    /// template (or [`Level::Level1`] for a template with no expressions).
+    ///
+    /// ```
+    /// # use uri_template_system_core::{ Level, Template };
+    /// #
+    /// let template = Template::parse("/users/{id}{#section}").unwrap();
+    ///
+    /// assert_eq!(Level::Level2, template.level());
+    ///
+    /// let template = Template::parse("/users/{id}{?fields*}").unwrap();
+    ///
+    /// assert_eq!(Level::Level4, template.level());
+    /// ```
+    #[must_use]
+    pub fn level(&self) -> Level {
+        self.expressions()
+            .map(Expression::level)
+            .max()
+            .unwrap_or(Level::Level1)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Expression
+
+impl Expression<'_> {
+    /// The minimum [`Level`] of implementation required to support the
+    /// expression, determined by the operator, the number of variables, and
+    /// the modifiers used.
+    #[must_use]
+    pub fn level(&self) -> Level {
+        if self
+            .variable_list
+            .iter()
+            .any(|variable| variable.modifier().is_some())
+        {
+            Level::Level4
+        } else if self.variable_list.len() > 1 {
+            Level::Level3
+        } else {
+            match self.operator {
+                Some(Operator::Level3(_)) => Level::Level3,
+                Some(Operator::Level2(_)) => Level::Level2,
+                None => Level::Level1,
+            }
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(raw: &str) -> Level {
+        Template::parse(raw).unwrap().level()
+    }
+
+    #[test]
+    fn template_level() {
+        assert_eq!(Level::Level1, level("/static"));
+        assert_eq!(Level::Level1, level("/{a}/{b}"));
+        assert_eq!(Level::Level2, level("/{a}/{+b}"));
+        assert_eq!(Level::Level3, level("/{a,b}"));
+        assert_eq!(Level::Level3, level("/{#a,b}"));
+        assert_eq!(Level::Level3, level("{?a}"));
+        assert_eq!(Level::Level4, level("/{a*}"));
+        assert_eq!(Level::Level4, level("{+a.b:3}"));
+    }
+}
@@ -0,0 +1,428 @@
+use std::{
+    collections::HashMap,
+    fmt::{
+        self,
+        Display,
+        Formatter,
+    },
+    ops::Range,
+};
+
+use crate::template::{
+    parse,
+    Component,
+    Expression,
+    Literal,
+    Modifier,
+    OpLevel2,
+    OpLevel3,
+    Operator,
+    Template,
+};
+
+// =============================================================================
+// Lint
+// =============================================================================
+
+// Types
+
+/// The [`Lint`] type represents a construct within a [`Template`] which is
+/// valid according to the grammar, but which is likely to be a mistake (see
+/// [`Template::lint`]).
+///
+/// Each lint has a [`LintId`], identifying the problem found, a [`Severity`],
+/// and a span (in bytes) of the input at which the problem occurs, which may be
+/// used to render the input with the problem underlined (see
+/// [`Lint::render`]).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Lint {
+    id: LintId,
+    span: Range<usize>,
+}
+
+impl Lint {
+    const fn new(id: LintId, span: Range<usize>) -> Self {
+        Self { id, span }
+    }
+
+    /// The [`LintId`] of the lint.
+    #[must_use]
+    pub const fn id(&self) -> LintId {
+        self.id
+    }
+
+    /// The [`Severity`] of the lint (as given by [`LintId::severity`]).
+    #[must_use]
+    pub const fn severity(&self) -> Severity {
+        self.id.severity()
+    }
+
+    /// The span (in bytes) of the input at which the problem occurs.
+    #[must_use]
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Renders the template input (which must be the input the template was
+    /// parsed from) with the span of the lint underlined, followed by a
+    /// description of the lint.
+    ///
+    /// ```
+    /// # use uri_template_system_core::{ LintId, Template };
+    /// #
+    /// let raw = "/search{?q}{?page}";
+    /// let lints = Template::parse(raw).unwrap().lint();
+    ///
+    /// assert_eq!(LintId::RepeatedQuery, lints[0].id());
+    /// assert_eq!(
+    ///     "/search{?q}{?page}\n           ^^^^^^^ warning[repeated-query]: query expression follows \
+    ///      an earlier query expression (expected '{&...}')",
+    ///     lints[0].render(raw)
+    /// );
+    /// ```
+    #[must_use]
+    pub fn render(&self, raw: &str) -> String {
+        parse::underline(raw, self.span.clone(), self)
+    }
+}
+
+impl Display for Lint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]: {}", self.severity(), self.id.name(), self.id)
+    }
+}
+
+/// The [`LintId`] type identifies the problem found by a [`Lint`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum LintId {
+    /// A variable is referenced more than once with different modifiers, as in
+    /// `{/id}{?id:3}`, which is legal but produces inconsistent values for
+    /// what is presumably the same thing.
+    ConflictingModifiers,
+    /// A prefix modifier is longer than is realistic (more than 255
+    /// characters), as in `{var:1000}`, which is legal (up to 9999) but is
+    /// unlikely to truncate anything in practice.
+    ExcessivePrefix,
+    /// A query or fragment expression is combined with a literal `?` or `#`, as
+    /// in `/search?q=x{?page}`, which results in a second delimiter within the
+    /// expansion (a query continuation is usually intended for queries).
+    MixedDelimiter,
+    /// A path or path parameter expression follows the start of the query, as
+    /// in `{?q}{/path}`, which expands to path segments within the query.
+    QueryBeforePath,
+    /// A query expression follows an earlier query expression, as in
+    /// `{?x}{?y}`, which results in a second `?` within the expansion (a
+    /// query continuation, `{&y}`, is usually intended).
+    RepeatedQuery,
+    /// A reserved expression begins the host of the URI, as in
+    /// `http://{+host}/`, which allows reserved characters (such as `/` or
+    /// `@`) to alter the structure of the authority. Reserved expressions
+    /// following other host content (as in `http://example.com{+path}`) are
+    /// not reported.
+    ReservedInHost,
+}
+
+impl LintId {
+    /// The name of the lint, in kebab case (as used when rendering the lint).
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::ConflictingModifiers => "conflicting-modifiers",
+            Self::ExcessivePrefix => "excessive-prefix",
+            Self::MixedDelimiter => "mixed-delimiter",
+            Self::QueryBeforePath => "query-before-path",
+            Self::RepeatedQuery => "repeated-query",
+            Self::ReservedInHost => "reserved-in-host",
+        }
+    }
+
+    /// The [`Severity`] of the lint.
+    #[must_use]
+    pub const fn severity(self) -> Severity {
+        match self {
+            Self::ExcessivePrefix => Severity::Info,
+            _ => Severity::Warning,
+        }
+    }
+}
+
+impl Display for LintId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::ConflictingModifiers => "variable referenced with conflicting modifiers",
+            Self::ExcessivePrefix => "prefix modifier longer than 255 characters",
+            Self::MixedDelimiter => "query or fragment operator mixed with literal '?' or '#'",
+            Self::QueryBeforePath => "path expression follows the start of the query",
+            Self::RepeatedQuery => {
+                "query expression follows an earlier query expression (expected '{&...}')"
+            }
+            Self::ReservedInHost => "reserved expression within the host",
+        })
+    }
+}
+
+/// The [`Severity`] type gives the severity of a [`Lint`]. Severities are
+/// ordered, from least to most severe.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Severity {
+    /// The construct is unusual, but may be intended.
+    Info,
+    /// The construct is very likely to be a mistake.
+    Warning,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+        })
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Template
+
+impl Template<'_> {
+    /// Checks the template for constructs which are valid according to the
+    /// grammar, but which are likely to be mistakes, returning a [`Lint`] for
+    /// each problem found (ordered by position within the input). A template
+    /// with no problems returns no lints.
+    ///
+    /// ```
+    /// # use uri_template_system_core::{ LintId, Severity, Template };
+    /// #
+    /// let template = Template::parse("/search?q={q}{?page}").unwrap();
+    /// let lints = template.lint();
+    ///
+    /// assert_eq!(1, lints.len());
+    /// assert_eq!(LintId::MixedDelimiter, lints[0].id());
+    /// assert_eq!(Severity::Warning, lints[0].severity());
+    /// assert_eq!(13..20, lints[0].span());
+    ///
+    /// let template = Template::parse("/search{?q}{&page}").unwrap();
+    ///
+    /// assert!(template.lint().is_empty());
+    /// ```
+    #[must_use]
+    pub fn lint(&self) -> Vec<Lint> {
+        let mut lints = Vec::new();
+
+        lint_variables(self, &mut lints);
+        lint_structure(self, &mut lints);
+
+        lints.sort_by_key(|lint| lint.span.start);
+        lints
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Variables
+
+fn lint_variables(template: &Template<'_>, lints: &mut Vec<Lint>) {
+    let mut modifiers = HashMap::new();
+
+    for variable in template.variables() {
+        let modifier = variable.modifier();
+
+        if *modifiers.entry(variable.name().name()).or_insert(modifier) != modifier {
+            lints.push(Lint::new(LintId::ConflictingModifiers, variable.span()));
+        }
+
+        if let Some(Modifier::Prefix(length)) = modifier {
+            if length > PREFIX_LIMIT {
+                lints.push(Lint::new(LintId::ExcessivePrefix, variable.span()));
+            }
+        }
+    }
+}
+
+const PREFIX_LIMIT: usize = 255;
+
+// -----------------------------------------------------------------------------
+
+// Structure
+
+// The structure of the template is tracked in a single pass over the
+// components - which part of the URI has been reached, and whether query or
+// fragment expressions have been seen. Only the start of the host is tracked,
+// from a literal "://" to the first host content (a literal or expression), as
+// a reserved expression there can alter the structure of the authority. The
+// remainder of the authority is not reliably distinguishable from the path
+// (the host may end at a literal "/" or at an expression), so it is treated as
+// part of the path.
+
+#[derive(Clone, Copy, Default, Eq, PartialEq)]
+enum Part {
+    #[default]
+    Path,
+    Host,
+    Query,
+    Fragment,
+}
+
+#[derive(Default)]
+struct State {
+    part: Part,
+    query_expression: bool,
+    fragment_expression: bool,
+}
+
+fn lint_structure(template: &Template<'_>, lints: &mut Vec<Lint>) {
+    let mut state = State::default();
+
+    for component in template.components() {
+        match component {
+            Component::Literal(literal) => lint_literal(literal, &mut state, lints),
+            Component::Expression(expression) => lint_expression(expression, &mut state, lints),
+        }
+    }
+}
+
+fn lint_literal(literal: &Literal<'_>, state: &mut State, lints: &mut Vec<Lint>) {
+    let value = literal.value();
+    let start = literal.span().start;
+
+    for (position, c) in value.char_indices() {
+        let span = start + position..start + position + 1;
+
+        match c {
+            '?' if state.part != Part::Fragment => {
+                if state.query_expression {
+                    lints.push(Lint::new(LintId::MixedDelimiter, span));
+                }
+
+                state.part = Part::Query;
+            }
+            '#' => {
+                if state.fragment_expression {
+                    lints.push(Lint::new(LintId::MixedDelimiter, span));
+                }
+
+                state.part = Part::Fragment;
+            }
+            _ => {}
+        }
+    }
+
+    if matches!(state.part, Part::Path | Part::Host) {
+        state.part = match value.rfind("://") {
+            Some(position) if position + 3 == value.len() => Part::Host,
+            _ => Part::Path,
+        };
+    }
+}
+
+fn lint_expression(expression: &Expression<'_>, state: &mut State, lints: &mut Vec<Lint>) {
+    let span = expression.span();
+    let host = state.part == Part::Host;
+
+    if host {
+        state.part = Part::Path;
+    }
+
+    match expression.operator() {
+        Some(Operator::Level2(OpLevel2::Reserved)) if host => {
+            lints.push(Lint::new(LintId::ReservedInHost, span));
+        }
+        Some(Operator::Level2(OpLevel2::Fragment)) => {
+            if state.part == Part::Fragment && !state.fragment_expression {
+                lints.push(Lint::new(LintId::MixedDelimiter, span));
+            }
+
+            state.part = Part::Fragment;
+            state.fragment_expression = true;
+        }
+        Some(Operator::Level3(OpLevel3::Path | OpLevel3::PathParameter))
+            if state.part == Part::Query =>
+        {
+            lints.push(Lint::new(LintId::QueryBeforePath, span));
+        }
+        Some(Operator::Level3(OpLevel3::Query)) if state.part != Part::Fragment => {
+            if state.query_expression {
+                lints.push(Lint::new(LintId::RepeatedQuery, span));
+            } else if state.part == Part::Query {
+                lints.push(Lint::new(LintId::MixedDelimiter, span));
+            }
+
+            state.part = Part::Query;
+            state.query_expression = true;
+        }
+        Some(Operator::Level3(OpLevel3::QueryContinuation)) if state.part != Part::Fragment => {
+            state.part = Part::Query;
+        }
+        _ => {}
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lints(raw: &str) -> Vec<(LintId, Range<usize>)> {
+        Template::parse(raw)
+            .unwrap()
+            .lint()
+            .into_iter()
+            .map(|lint| (lint.id(), lint.span()))
+            .collect()
+    }
+
+    #[test]
+    fn lint_ids() {
+        use LintId::*;
+
+        assert_eq!(vec![(ConflictingModifiers, 9..11)], lints("{/id}{?q,id:3}"));
+        assert_eq!(vec![(ExcessivePrefix, 1..4)], lints("{x.y:1000}"));
+        assert_eq!(vec![(MixedDelimiter, 9..16)], lints("/search?q{?page}"));
+        assert_eq!(vec![(MixedDelimiter, 4..5)], lints("{?q}?x"));
+        assert_eq!(vec![(MixedDelimiter, 2..9)], lints("/#{#frag}"));
+        assert_eq!(vec![(QueryBeforePath, 4..11)], lints("{?q}{/path}"));
+        assert_eq!(vec![(RepeatedQuery, 4..8)], lints("{?x}{?y}"));
+        assert_eq!(vec![(ReservedInHost, 7..14)], lints("http://{+host}/"));
+    }
+
+    #[test]
+    fn lint_clean() {
+        let raws = [
+            "http://{host}{/path*}{?q,page}{&lang}{#section}",
+            "http://example.com/{+path}",
+            "http://example.com{+path}",
+            "https://api.example.com{+path}{?q}",
+            "http://example.com:8080{+path}",
+            "http://{host}{+path}",
+            "{+base}/path{/id}",
+            "/search?q={q}{&page}",
+            "/#/route{/id}?x",
+        ];
+
+        for raw in raws {
+            assert_eq!(Vec::<(LintId, Range<usize>)>::new(), lints(raw), "{raw}");
+        }
+    }
+
+    #[test]
+    fn lint_order_and_severity() {
+        let lints = Template::parse("{?q}{?q:1000}{/path}").unwrap().lint();
+
+        assert_eq!(
+            vec![
+                (LintId::RepeatedQuery, Severity::Warning),
+                (LintId::ConflictingModifiers, Severity::Warning),
+                (LintId::ExcessivePrefix, Severity::Info),
+                (LintId::QueryBeforePath, Severity::Warning),
+            ],
+            lints
+                .iter()
+                .map(|lint| (lint.id(), lint.severity()))
+                .collect::<Vec<_>>()
+        );
+    }
+}
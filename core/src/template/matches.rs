@@ -0,0 +1,551 @@
+use std::collections::HashSet;
+
+use crate::{
+    string::{
+        decode::decode,
+        encode::Encode,
+        satisfy::{
+            self,
+            Ascii,
+            PercentEncoded,
+            Satisfy,
+        },
+    },
+    template::{
+        expand::{
+            Allow,
+            Behaviour,
+            DEFAULT_BEHAVIOUR,
+        },
+        Component,
+        Expression,
+        Literal,
+        Modifier,
+        Operator,
+        VariableSpecification,
+    },
+    value::Value,
+};
+
+// =============================================================================
+// Match
+// =============================================================================
+
+// Types
+
+/// Values bound during matching, along with an indication of whether the value
+/// is partial (bound from a prefix modified variable).
+pub type Matched = Vec<(String, Value, bool)>;
+
+// -----------------------------------------------------------------------------
+
+// Functions
+
+/// Attempts to match the whole of the raw input against the given components,
+/// accumulating bound values. Expressions are matched greedily, backtracking to
+/// shorter matches where the remaining components fail to match.
+///
+/// Backtracking alone is exponential in the number of adjacent expressions, so
+/// failures are remembered by component index and remaining input, and not
+/// retried. This is only sound where the remaining components reference no
+/// variable bound by an earlier component (as a repeated variable must match
+/// the value already bound), so failures of such components are not
+/// remembered.
+pub fn match_components(components: &[Component<'_>], raw: &str, matched: &mut Matched) -> bool {
+    let mut state = State {
+        independent: independent(components),
+        failed: HashSet::new(),
+    };
+
+    match_from(components, 0, raw, matched, &mut state)
+}
+
+struct State {
+    independent: Vec<bool>,
+    failed: HashSet<(usize, usize)>,
+}
+
+fn match_from(
+    components: &[Component<'_>],
+    index: usize,
+    raw: &str,
+    matched: &mut Matched,
+    state: &mut State,
+) -> bool {
+    let key = (index, raw.len());
+
+    if state.failed.contains(&key) {
+        return false;
+    }
+
+    let result = match components.get(index) {
+        None => raw.is_empty(),
+        Some(Component::Literal(literal)) => literal.matches(raw).map_or(false, |position| {
+            match_from(components, index + 1, &raw[position..], matched, state)
+        }),
+        Some(Component::Expression(expression)) => {
+            (0..=expression.extent(raw)).rev().any(|position| {
+                let length = matched.len();
+
+                if expression.matches(&raw[..position], matched)
+                    && match_from(components, index + 1, &raw[position..], matched, state)
+                {
+                    true
+                } else {
+                    matched.truncate(length);
+                    false
+                }
+            })
+        }
+    };
+
+    if !result && state.independent[index] {
+        state.failed.insert(key);
+    }
+
+    result
+}
+
+/// Whether the components from each index onwards reference no variable which
+/// is also referenced before that index.
+fn independent(components: &[Component<'_>]) -> Vec<bool> {
+    fn names<'a>(component: &'a Component<'_>) -> Vec<&'a str> {
+        match component {
+            Component::Expression(expression) => expression
+                .variables()
+                .iter()
+                .map(|variable| variable.name().name())
+                .collect(),
+            Component::Literal(_) => Vec::new(),
+        }
+    }
+
+    (0..=components.len())
+        .map(|index| {
+            let before = components[..index]
+                .iter()
+                .flat_map(names)
+                .collect::<HashSet<_>>();
+
+            components[index..]
+                .iter()
+                .flat_map(names)
+                .all(|name| !before.contains(name))
+        })
+        .collect()
+}
+
+fn bind(matched: &mut Matched, name: &str, value: Value, partial: bool) -> bool {
+    // Where a variable appears more than once in a template, each occurrence
+    // must match the same value for the template as a whole to match (or a
+    // consistent value, where one or both occurrences are partial).
+
+    if let Some((_, matched_value, matched_partial)) = matched
+        .iter_mut()
+        .find(|(matched_name, ..)| matched_name == name)
+    {
+        match (&*matched_value, &value) {
+            _ if *matched_value == value => {
+                *matched_partial &= partial;
+                true
+            }
+            (Value::Item(existing), Value::Item(item))
+                if *matched_partial && item.starts_with(existing.as_str()) =>
+            {
+                *matched_value = value;
+                *matched_partial = partial;
+                true
+            }
+            (Value::Item(existing), Value::Item(item)) => {
+                partial && existing.starts_with(item.as_str())
+            }
+            _ => false,
+        }
+    } else {
+        matched.push((name.into(), value, partial));
+        true
+    }
+}
+
+// =============================================================================
+// Match - Implementations
+// =============================================================================
+
+// Expression
+
+impl Expression<'_> {
+    fn behaviour(&self) -> &Behaviour {
+        self.operator
+            .map_or(&DEFAULT_BEHAVIOUR, Operator::behaviour)
+    }
+
+    /// The maximum length of the raw input which could have been produced by
+    /// expansion of this expression, given the characters which the expression
+    /// operator allows (and may emit as separators).
+    fn extent(&self, raw: &str) -> usize {
+        let behaviour = self.behaviour();
+        let first = behaviour
+            .first
+            .filter(|c| raw.starts_with(*c))
+            .map_or(0, char::len_utf8);
+
+        let satisfier: Box<dyn Satisfy> = match behaviour.allow {
+            Allow::U => {
+                let sep = behaviour.sep;
+
+                Box::new((
+                    Ascii::new(move |b| {
+                        satisfy::is_unreserved_ascii(b)
+                            || char::from(b) == sep
+                            || b == b','
+                            || b == b'='
+                    }),
+                    PercentEncoded,
+                ))
+            }
+            Allow::UR => behaviour.allow.satisfier(),
+        };
+
+        first + satisfier.satisfy(&raw[first..])
+    }
+
+    fn matches(&self, raw: &str, matched: &mut Matched) -> bool {
+        // An empty match implies that all variables in the expression were
+        // undefined at expansion, so nothing is bound.
+
+        if raw.is_empty() {
+            return true;
+        }
+
+        let behaviour = self.behaviour();
+        let rest = match behaviour.first {
+            Some(c) => match raw.strip_prefix(c) {
+                Some(rest) => rest,
+                None => return false,
+            },
+            None => raw,
+        };
+
+        let pieces = rest.split(behaviour.sep).collect::<Vec<_>>();
+        let bound = if behaviour.named {
+            self.bind_named(&pieces, behaviour)
+        } else {
+            self.bind_unnamed(&pieces, behaviour)
+        };
+
+        bound.map_or(false, |bound| {
+            bound.into_iter().all(|(variable, value)| {
+                let partial = matches!(variable.modifier(), Some(Modifier::Prefix(_)));
+
+                bind(matched, variable.name().name(), value, partial)
+            })
+        })
+    }
+
+    fn bind_named(
+        &self,
+        pieces: &[&str],
+        behaviour: &Behaviour,
+    ) -> Option<Vec<(&VariableSpecification<'_>, Value)>> {
+        let specs = &self.variable_list;
+        let mut bound: Vec<Option<Value>> = vec![None; specs.len()];
+        let mut index = 0;
+
+        for piece in pieces {
+            let (name, value) = match piece.split_once('=') {
+                Some((name, value)) => (name, value),
+                None if behaviour.ifemp.is_none() => (*piece, ""),
+                None => return None,
+            };
+
+            // Each piece binds to the next variable with a matching name, or failing
+            // that, to the next exploded variable (as a member of an associative
+            // array). Variable names are compared as written in the template, as
+            // they are not encoded further during expansion.
+
+            let position = index
+                + specs[index..]
+                    .iter()
+                    .position(|variable| variable.name().name() == name)
+                    .or_else(|| {
+                        specs[index..].iter().position(|variable| {
+                            matches!(variable.modifier(), Some(Modifier::Explode))
+                        })
+                    })?;
+
+            let variable = &specs[position];
+            let explode = matches!(variable.modifier(), Some(Modifier::Explode));
+
+            match (
+                &mut bound[position],
+                explode,
+                variable.name().name() == name,
+            ) {
+                (slot @ None, false, true) => {
+                    *slot = Some(split(value)?);
+                    index = position + 1;
+                }
+                (slot @ None, true, true) => {
+                    *slot = Some(Value::List(vec![decode(value)?]));
+                    index = position;
+                }
+                (slot @ None, true, false) => {
+                    *slot = Some(Value::AssociativeArray(vec![(
+                        decode(name)?,
+                        decode(value)?,
+                    )]));
+                    index = position;
+                }
+                (Some(Value::List(list)), true, true) => list.push(decode(value)?),
+                (Some(Value::AssociativeArray(array)), true, false) => {
+                    array.push((decode(name)?, decode(value)?));
+                }
+                _ => return None,
+            }
+        }
+
+        Some(
+            specs
+                .iter()
+                .zip(bound)
+                .filter_map(|(variable, value)| value.map(|value| (variable, value)))
+                .collect(),
+        )
+    }
+
+    fn bind_unnamed(
+        &self,
+        pieces: &[&str],
+        behaviour: &Behaviour,
+    ) -> Option<Vec<(&VariableSpecification<'_>, Value)>> {
+        let specs = &self.variable_list;
+        let mut bound = Vec::new();
+        let mut pieces = pieces;
+
+        for (i, variable) in specs.iter().enumerate() {
+            if pieces.is_empty() {
+                break;
+            }
+
+            let remaining = specs.len() - i - 1;
+            let value = match variable.modifier() {
+                Some(Modifier::Explode) => {
+                    // An exploded variable takes as many pieces as possible, leaving one
+                    // for each of the remaining variables.
+
+                    let (taken, rest) =
+                        pieces.split_at(pieces.len().saturating_sub(remaining).max(1));
+
+                    pieces = rest;
+                    exploded(taken, behaviour)?
+                }
+                _ if remaining == 0 && pieces.len() > 1 => {
+                    // The final variable takes all remaining pieces, where those pieces may
+                    // legitimately have been produced by a single value.
+
+                    let value = match behaviour.allow {
+                        Allow::U if behaviour.sep == ',' => Value::List(
+                            pieces
+                                .iter()
+                                .map(|piece| decode(piece))
+                                .collect::<Option<_>>()?,
+                        ),
+                        Allow::U if behaviour.sep == '.' => split(&pieces.join("."))?,
+                        Allow::U => return None,
+                        Allow::UR => Value::Item(decode(&pieces.join(","))?),
+                    };
+
+                    pieces = &[];
+                    value
+                }
+                _ => {
+                    let (taken, rest) = pieces.split_first()?;
+
+                    pieces = rest;
+                    split(taken)?
+                }
+            };
+
+            bound.push((variable, value));
+        }
+
+        pieces.is_empty().then_some(bound)
+    }
+}
+
+fn exploded(pieces: &[&str], behaviour: &Behaviour) -> Option<Value> {
+    match (pieces, &behaviour.allow) {
+        ([first, ..], Allow::UR) if first.contains('=') => {
+            // Where reserved characters are allowed, values may contain an unencoded
+            // separator, so pieces which are not pairs are treated as a continuation
+            // of the preceding value.
+
+            let mut array = Vec::new();
+
+            for piece in pieces {
+                match piece.split_once('=') {
+                    Some((name, value)) => array.push((name.to_owned(), value.to_owned())),
+                    None => {
+                        if let Some((_, value)) = array.last_mut() {
+                            value.push(behaviour.sep);
+                            value.push_str(piece);
+                        }
+                    }
+                }
+            }
+
+            array
+                .into_iter()
+                .map(|(name, value)| Some((decode(&name)?, decode(&value)?)))
+                .collect::<Option<_>>()
+                .map(Value::AssociativeArray)
+        }
+        _ if pieces.iter().all(|piece| piece.contains('=')) => pieces
+            .iter()
+            .map(|piece| {
+                piece
+                    .split_once('=')
+                    .and_then(|(name, value)| Some((decode(name)?, decode(value)?)))
+            })
+            .collect::<Option<_>>()
+            .map(Value::AssociativeArray),
+        _ => pieces
+            .iter()
+            .map(|piece| decode(piece))
+            .collect::<Option<_>>()
+            .map(Value::List),
+    }
+}
+
+fn split(raw: &str) -> Option<Value> {
+    // An unencoded comma within a single value can only have been produced as a
+    // list separator (associative arrays are indistinguishable from lists when
+    // not exploded, and are matched as lists).
+
+    if raw.contains(',') {
+        raw.split(',')
+            .map(decode)
+            .collect::<Option<_>>()
+            .map(Value::List)
+    } else {
+        decode(raw).map(Value::Item)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Literal
+
+impl Literal<'_> {
+    fn matches(&self, raw: &str) -> Option<usize> {
+        let mut expanded = String::default();
+
+        expanded
+            .encode(&self.value, &satisfy::unreserved_or_reserved())
+            .ok()?;
+
+        raw.starts_with(&expanded).then_some(expanded.len())
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::mpsc,
+        thread,
+        time::Duration,
+    };
+
+    use crate::{
+        Template,
+        Value,
+        Values,
+    };
+
+    fn match_uri(template: &str, uri: &str) -> Option<Values> {
+        Template::parse(template).unwrap().match_uri(uri)
+    }
+
+    #[test]
+    fn literal_matching() {
+        assert_eq!(Some(Values::default()), match_uri("/a/b", "/a/b"));
+        assert_eq!(None, match_uri("/a/b", "/a/c"));
+        assert_eq!(None, match_uri("/a/b", "/a/b/c"));
+    }
+
+    #[test]
+    fn simple_matching() {
+        assert_eq!(
+            Some(Values::default().add("id", Value::item("hello world"))),
+            match_uri("/users/{id}", "/users/hello%20world")
+        );
+        assert_eq!(None, match_uri("/users/{id}", "/users/a/b"));
+    }
+
+    #[test]
+    fn path_matching() {
+        assert_eq!(
+            Some(
+                Values::default()
+                    .add("a", Value::item("x"))
+                    .add("b", Value::list(["y", "z"]))
+            ),
+            match_uri("{/a,b*}", "/x/y/z")
+        );
+    }
+
+    #[test]
+    fn query_matching() {
+        assert_eq!(
+            Some(
+                Values::default()
+                    .add("q", Value::item("rust"))
+                    .add("tags", Value::list(["a", "b"]))
+            ),
+            match_uri("/search{?q,page,tags}", "/search?q=rust&tags=a,b")
+        );
+        assert_eq!(
+            Some(Values::default().add(
+                "keys",
+                Value::associative_array([("semi", ";"), ("dot", ".")])
+            )),
+            match_uri("{?keys*}", "?semi=%3B&dot=.")
+        );
+    }
+
+    #[test]
+    fn reserved_matching() {
+        assert_eq!(
+            Some(Values::default().add("path", Value::item("/foo/bar"))),
+            match_uri("{+path}/here", "/foo/bar/here")
+        );
+    }
+
+    #[test]
+    fn repeated_variable_matching() {
+        assert!(match_uri("{a}/{a}", "x/x").is_some());
+        assert!(match_uri("{a}/{a}", "x/y").is_none());
+        assert!(match_uri("{a}/{b}{c}/{a}", "x/yz/x").is_some());
+        assert!(match_uri("{a}/{b}{c}/{a}", "x/yz/y").is_none());
+    }
+
+    #[test]
+    fn adjacent_expression_matching() {
+        // Matching adjacent expressions against input which can not match must
+        // not be exponential in the number of expressions (the input is matched
+        // on another thread, so that a regression fails rather than hangs).
+
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            let template = Template::parse("{a}{b}{c}{d}{e}{f}{g}x").unwrap();
+            sender.send(template.match_uri(&"a".repeat(60))).unwrap();
+        });
+
+        assert_eq!(None, receiver.recv_timeout(Duration::from_secs(5)).unwrap());
+    }
+}
@@ -1,3 +1,8 @@
+use std::{
+    fmt::Display,
+    ops::Range,
+};
+
 use thiserror::Error;
 
 use crate::{
@@ -8,6 +13,7 @@ use crate::{
         Unicode,
     },
     template::{
+        level::Level,
         Component,
         Expression,
         Literal,
@@ -19,6 +25,7 @@ use crate::{
         VariableList,
         VariableName,
         VariableSpecification,
+        PREFIX_RANGE,
     },
 };
 
@@ -28,14 +35,6 @@ use crate::{
 
 // Traits
 
-#[allow(clippy::module_name_repetitions)]
-pub trait Parse<'t>
-where
-    Self: Sized,
-{
-    fn parse(raw: &'t str, global: usize) -> (usize, Self);
-}
-
 #[allow(clippy::module_name_repetitions)]
 pub trait TryParse<'t>
 where
@@ -51,24 +50,183 @@ where
 /// An [`Error`](std::error::Error) compatible type which may be the result of a
 /// failure of [`Template::parse`], likely due to an invalid URI Template format
 /// (as defined by the grammar given in [RFC6570](https://datatracker.ietf.org/doc/html/rfc6570)).
+///
+/// Each error has a [`ParseErrorKind`], describing the problem found, and a
+/// span (in bytes) of the input at which the problem occurs, which may be used
+/// to render the input with the problem underlined (see
+/// [`ParseError::render`]).
 #[allow(clippy::module_name_repetitions)]
-#[derive(Debug, Error)]
-pub enum ParseError {
-    /// The input given contained an unexpected value according to the URI
-    /// Template value grammar, causing parsing to fail. See the grammar at
-    /// [RFC6570](https://datatracker.ietf.org/doc/html/rfc6570) for the definition of a
-    /// valid URI Template.
-    #[error("{message} at position: {position}. expected: {expected}.")]
-    UnexpectedInput {
-        /// The position (in bytes) of the input at which the unexpected input
-        /// occurs.
-        position: usize,
-        /// A message giving more detail about which grammatical element failed
-        /// to parse the given input.
-        message: String,
-        /// An indication of what (valid) input was expected by the parser.
-        expected: String,
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+#[error("{kind} at position: {}", span.start)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    span: Range<usize>,
+}
+
+impl ParseError {
+    const fn new(kind: ParseErrorKind, span: Range<usize>) -> Self {
+        Self { kind, span }
+    }
+
+    /// The [`ParseErrorKind`] of the error.
+    #[must_use]
+    pub const fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// The span (in bytes) of the input at which the error occurs. The span
+    /// may be empty where the error is caused by missing input.
+    #[must_use]
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Renders the given input (which should be the input given to the parser)
+    /// with the span of the error underlined with carets, followed by a
+    /// description of the error, for use in command line or log output.
+    ///
+    /// ```
+    /// # use uri_template_system_core::{ ParseErrorKind, Template };
+    /// #
+    /// let raw = "/users/{id";
+    /// let err = Template::parse(raw).unwrap_err();
+    ///
+    /// assert_eq!(ParseErrorKind::UnclosedExpression, err.kind());
+    /// assert_eq!(
+    ///     "/users/{id\n       ^^^ unclosed expression (expected '}')",
+    ///     err.render(raw)
+    /// );
+    /// ```
+    #[must_use]
+    pub fn render(&self, raw: &str) -> String {
+        underline(raw, self.span.clone(), &self.kind)
+    }
+}
+
+// The input followed by a line underlining the span (in characters rather than
+// bytes, so that the carets line up with the input when displayed) and the
+// given message.
+
+pub fn underline(raw: &str, span: Range<usize>, message: &impl Display) -> String {
+    let width = |range: Range<usize>| raw.get(range).map_or(0, |s| s.chars().count());
+    let offset = width(0..span.start);
+    let length = width(span).max(1);
+
+    format!(
+        "{raw}\n{}{} {message}",
+        " ".repeat(offset),
+        "^".repeat(length)
+    )
+}
+
+/// The kind of a [`ParseError`], describing the problem found in the input
+/// according to the URI Template grammar (see
+/// [RFC6570](https://datatracker.ietf.org/doc/html/rfc6570)).
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Copy, Debug, Eq, Error, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// A variable name was expected but not found, as in `{}` or `{a,}` (see
+    /// [RFC6570 2.3](https://datatracker.ietf.org/doc/html/rfc6570#section-2.3)).
+    #[error("empty variable name")]
+    EmptyVarname,
+    /// A character was found which is not valid within a literal (see
+    /// [RFC6570 2.1](https://datatracker.ietf.org/doc/html/rfc6570#section-2.1)).
+    #[error("invalid literal character")]
+    InvalidLiteralChar,
+    /// A prefix modifier did not begin with a digit from 1 to 9 (see
+    /// [RFC6570 2.4.1](https://datatracker.ietf.org/doc/html/rfc6570#section-2.4.1)).
+    #[error("invalid prefix modifier (expected an integer from 1 to 9999)")]
+    InvalidPrefix,
+    /// A character was found which is not valid within a variable name (see
+    /// [RFC6570 2.3](https://datatracker.ietf.org/doc/html/rfc6570#section-2.3)).
+    #[error("invalid variable name character")]
+    InvalidVarnameChar,
+    /// An expression was found which requires a higher [`Level`] of
+    /// implementation than the maximum level allowed (see
+    /// [`ParseOptions::max_level`]).
+    #[error("expression requires {required} (maximum allowed is {max})")]
+    LevelExceeded {
+        /// The level required by the expression.
+        required: Level,
+        /// The maximum level allowed.
+        max: Level,
     },
+    /// A prefix modifier was greater than the maximum of 9999 (see
+    /// [RFC6570 2.4.1](https://datatracker.ietf.org/doc/html/rfc6570#section-2.4.1)).
+    #[error("prefix modifier out of range (expected an integer from 1 to 9999)")]
+    PrefixOutOfRange,
+    /// An operator was found which is reserved for future extensions (one of
+    /// `=`, `,`, `!`, `@`, or `|`, see
+    /// [RFC6570 2.2](https://datatracker.ietf.org/doc/html/rfc6570#section-2.2)).
+    #[error("reserved operator (reserved for future extensions)")]
+    ReservedOperator,
+    /// An expression was not closed before the end of the input (or the start
+    /// of another expression).
+    #[error("unclosed expression (expected '}}')")]
+    UnclosedExpression,
+}
+
+// -----------------------------------------------------------------------------
+
+// Options
+
+/// The [`ParseOptions`] type configures the behaviour of parsing (see
+/// [`Template::parse_with`]). The default options give parsing as defined by
+/// the RFC, accepting templates up to and including Level 4.
+///
+/// ```
+/// # use uri_template_system_core::{ Level, ParseErrorKind, ParseOptions, Template };
+/// #
+/// let options = ParseOptions::default().max_level(Level::Level2);
+///
+/// assert!(Template::parse_with("/users/{id}{#section}", &options).is_ok());
+/// assert!(matches!(
+///     Template::parse_with("/users/{id}{?q}", &options).map_err(|err| err.kind()),
+///     Err(ParseErrorKind::LevelExceeded {
+///         required: Level::Level3,
+///         ..
+///     })
+/// ));
+/// ```
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug)]
+pub struct ParseOptions {
+    max_level: Level,
+}
+
+impl ParseOptions {
+    /// Sets the maximum [`Level`] of template accepted by parsing, where any
+    /// expression requiring a higher level is an error, and returns the
+    /// modified options to allow for chaining of calls during construction.
+    #[must_use]
+    pub const fn max_level(mut self, max_level: Level) -> Self {
+        self.max_level = max_level;
+        self
+    }
+
+    pub(crate) fn check(&self, template: &Template<'_>) -> Result<(), ParseError> {
+        template
+            .expressions()
+            .find(|expression| expression.level() > self.max_level)
+            .map_or(Ok(()), |expression| {
+                Err(ParseError::new(
+                    ParseErrorKind::LevelExceeded {
+                        required: expression.level(),
+                        max: self.max_level,
+                    },
+                    expression.span(),
+                ))
+            })
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            max_level: Level::Level4,
+        }
+    }
 }
 
 // =============================================================================
@@ -90,37 +248,80 @@ impl<'t> TryParse<'t> for Template<'t> {
 
 impl<'t> TryParse<'t> for Vec<Component<'t>> {
     fn try_parse(raw: &'t str, global: usize) -> Result<(usize, Self), ParseError> {
-        let mut parsed_components = Self::new(); // TODO: Check if a default capacity estimation improves perf
-        let mut state = ComponentState::default();
+        parse_components(raw, global, false)
+            .map(|components| (raw.len(), components))
+            .map_err(|mut errors| errors.remove(0))
+    }
+}
 
-        loop {
-            let rest = &raw[state.position..];
+/// Parses the given input as components, returning all errors found where
+/// parsing fails. Where `recover` is set, parsing continues after an error (at
+/// the end of the invalid expression or the invalid literal character), so
+/// that every error in the input is found - otherwise parsing stops at the
+/// first error.
+pub fn parse_components(
+    raw: &str,
+    global: usize,
+    recover: bool,
+) -> Result<Vec<Component<'_>>, Vec<ParseError>> {
+    let mut parsed_components = Vec::new(); // TODO: Check if a default capacity estimation improves perf
+    let mut errors = Vec::new();
+    let mut state = ComponentState::default();
+
+    loop {
+        let rest = &raw[state.position..];
+
+        if rest.is_empty() {
+            break;
+        }
 
-            if rest.is_empty() {
-                break;
+        let parsed = if rest.starts_with('{') {
+            Expression::try_parse(rest, global + state.position)
+                .map(|(position, expression)| (position, Component::Expression(expression)))
+        } else {
+            Literal::try_parse(rest, global + state.position)
+                .map(|(position, literal)| (position, Component::Literal(literal)))
+        };
+
+        match parsed {
+            Ok((position, component)) => {
+                parsed_components.push(component);
+                state.position += position;
             }
-
-            let parsed = if rest.starts_with('{') {
-                Expression::try_parse(rest, global + state.position)
-                    .map(|(position, expression)| (position, Component::Expression(expression)))
-            } else {
-                Literal::try_parse(rest, global + state.position)
-                    .map(|(position, literal)| (position, Component::Literal(literal)))
-            };
-
-            match parsed {
-                Ok((position, component)) => {
-                    parsed_components.push(component);
-                    state.position += position;
-                }
-                Err(err) => return Err(err),
+            Err(err) if recover => {
+                errors.push(err);
+                state.position += recovery_position(rest);
             }
+            Err(err) => return Err(vec![err]),
         }
+    }
 
-        Ok((raw.len(), parsed_components))
+    if errors.is_empty() {
+        Ok(parsed_components)
+    } else {
+        Err(errors)
     }
 }
 
+// The position at which to continue parsing after an error in the component
+// beginning the given input - after the closing brace of an invalid expression
+// (or at the start of the next expression, or the end of the input, where the
+// expression is not closed), or after an invalid literal character.
+
+fn recovery_position(rest: &str) -> usize {
+    rest.strip_prefix('{').map_or_else(
+        || rest.chars().next().map_or(rest.len(), char::len_utf8),
+        |expression| {
+            expression.find(['{', '}']).map_or(rest.len(), |position| {
+                match expression.as_bytes()[position] {
+                    b'}' => position + 2,
+                    _ => position + 1,
+                }
+            })
+        },
+    )
+}
+
 #[derive(Default)]
 struct ComponentState {
     position: usize,
@@ -140,24 +341,22 @@ impl<'t> TryParse<'t> for Expression<'t> {
             let rest = &raw[state.position..];
 
             match &state.next {
-                ExpressionNext::OpeningBrace if rest.starts_with('{') => {
+                ExpressionNext::OpeningBrace => {
+                    // Expressions are only parsed where the input begins with
+                    // an opening brace.
+
                     state.next = ExpressionNext::Operator;
                     state.position += 1;
                 }
-                ExpressionNext::OpeningBrace => {
-                    return Err(ParseError::UnexpectedInput {
-                        position: global + state.position,
-                        message: "unexpected input when parsing expression component".into(),
-                        expected: "opening brace ('{')".into(),
-                    });
-                }
                 ExpressionNext::Operator => {
-                    let (position, operator) =
-                        Option::<Operator>::parse(rest, global + state.position);
-
-                    parsed_operator = operator;
-                    state.next = ExpressionNext::VariableList;
-                    state.position += position;
+                    match Option::<Operator>::try_parse(rest, global + state.position) {
+                        Ok((position, operator)) => {
+                            parsed_operator = operator;
+                            state.next = ExpressionNext::VariableList;
+                            state.position += position;
+                        }
+                        Err(err) => return Err(err),
+                    }
                 }
                 ExpressionNext::VariableList => {
                     match VariableList::try_parse(rest, global + state.position) {
@@ -166,6 +365,12 @@ impl<'t> TryParse<'t> for Expression<'t> {
                             state.next = ExpressionNext::ClosingBrace;
                             state.position += position;
                         }
+                        Err(err)
+                            if err.kind == ParseErrorKind::EmptyVarname
+                                && err.span.start == global + raw.len() =>
+                        {
+                            return Err(unclosed(raw, global));
+                        }
                         Err(err) => return Err(err),
                     }
                 }
@@ -174,21 +379,37 @@ impl<'t> TryParse<'t> for Expression<'t> {
 
                     return Ok((
                         state.position,
-                        Self::new(parsed_operator, parsed_variable_list),
+                        Self::new(
+                            parsed_operator,
+                            parsed_variable_list,
+                            global..global + state.position,
+                        ),
                     ));
                 }
+                ExpressionNext::ClosingBrace if rest.is_empty() || rest.starts_with('{') => {
+                    return Err(unclosed(&raw[..state.position], global));
+                }
                 ExpressionNext::ClosingBrace => {
-                    return Err(ParseError::UnexpectedInput {
-                        position: global + state.position,
-                        message: "unexpected input when parsing expression component".into(),
-                        expected: "closing brace ('}')".into(),
-                    });
+                    return Err(ParseError::new(
+                        ParseErrorKind::InvalidVarnameChar,
+                        char_span(rest, global + state.position),
+                    ));
                 }
             }
         }
     }
 }
 
+// An unclosed expression error, spanning the expression to the point at which
+// it should have been closed.
+
+const fn unclosed(raw: &str, global: usize) -> ParseError {
+    ParseError::new(
+        ParseErrorKind::UnclosedExpression,
+        global..global + raw.len(),
+    )
+}
+
 #[derive(Default)]
 struct ExpressionState {
     next: ExpressionNext,
@@ -256,8 +477,14 @@ enum VariableListNext {
 impl<'t> TryParse<'t> for VariableSpecification<'t> {
     fn try_parse(raw: &'t str, global: usize) -> Result<(usize, Self), ParseError> {
         VariableName::try_parse(raw, global).and_then(|(position_a, variable_name)| {
-            Option::<Modifier>::try_parse(&raw[position_a..], global + position_a)
-                .map(|(position_b, modifier)| (position_a + position_b, (variable_name, modifier)))
+            Option::<Modifier>::try_parse(&raw[position_a..], global + position_a).map(
+                |(position_b, modifier)| {
+                    (
+                        position_a + position_b,
+                        VariableSpecification::new(variable_name, modifier),
+                    )
+                },
+            )
         })
     }
 }
@@ -270,25 +497,35 @@ impl<'t> TryParse<'t> for VariableName<'t> {
             let rest = &raw[state.position..];
 
             match &state.next {
-                VariableNameNext::VariableCharacters => match variable_name_satisfier().satisfy(rest) {
-                    0 => {
-                        return Err(ParseError::UnexpectedInput {
-                            position: global + state.position,
-                            message: "unexpected input parsing variable name".into(),
-                            expected: "valid var_name characters (see: https://datatracker.ietf.org/doc/html/rfc6570#section-2.3)".into(),
-                        })
-                    }
-                    n => {
-                        state.position += n;
-                        state.next = VariableNameNext::Dot;
+                VariableNameNext::VariableCharacters => {
+                    match variable_name_satisfier().satisfy(rest) {
+                        0 if rest.is_empty() || rest.starts_with(['}', ',']) => {
+                            return Err(ParseError::new(
+                                ParseErrorKind::EmptyVarname,
+                                global + state.position..global + state.position,
+                            ));
+                        }
+                        0 => {
+                            return Err(ParseError::new(
+                                ParseErrorKind::InvalidVarnameChar,
+                                char_span(rest, global + state.position),
+                            ));
+                        }
+                        n => {
+                            state.position += n;
+                            state.next = VariableNameNext::Dot;
+                        }
                     }
-                },
+                }
                 VariableNameNext::Dot if rest.starts_with('.') => {
                     state.position += 1;
                     state.next = VariableNameNext::VariableCharacters;
                 }
                 VariableNameNext::Dot => {
-                    return Ok((state.position, VariableName::new(&raw[..state.position])));
+                    return Ok((
+                        state.position,
+                        VariableName::new(&raw[..state.position], global..global + state.position),
+                    ));
                 }
             }
         }
@@ -329,25 +566,30 @@ const fn is_variable_character_ascii(b: u8) -> bool {
 
 // Operator
 
-impl<'t> Parse<'t> for Option<Operator> {
-    fn parse(raw: &'t str, _global: usize) -> (usize, Self) {
-        raw.chars()
-            .next()
-            .and_then(|c| {
-                let operator = match c {
-                    '+' => Some(Operator::Level2(OpLevel2::Reserved)),
-                    '#' => Some(Operator::Level2(OpLevel2::Fragment)),
-                    '.' => Some(Operator::Level3(OpLevel3::Label)),
-                    '/' => Some(Operator::Level3(OpLevel3::Path)),
-                    ';' => Some(Operator::Level3(OpLevel3::PathParameter)),
-                    '?' => Some(Operator::Level3(OpLevel3::Query)),
-                    '&' => Some(Operator::Level3(OpLevel3::QueryContinuation)),
-                    _ => None,
-                };
-
-                operator.map(|operator| (1, Some(operator)))
-            })
-            .unwrap_or((0, None))
+// Operators reserved for future extensions (see
+// https://datatracker.ietf.org/doc/html/rfc6570#section-2.2) are recognised
+// explicitly, rather than failing later as invalid variable name characters.
+
+impl<'t> TryParse<'t> for Option<Operator> {
+    fn try_parse(raw: &'t str, global: usize) -> Result<(usize, Self), ParseError> {
+        let operator = match raw.chars().next() {
+            Some('+') => Operator::Level2(OpLevel2::Reserved),
+            Some('#') => Operator::Level2(OpLevel2::Fragment),
+            Some('.') => Operator::Level3(OpLevel3::Label),
+            Some('/') => Operator::Level3(OpLevel3::Path),
+            Some(';') => Operator::Level3(OpLevel3::PathParameter),
+            Some('?') => Operator::Level3(OpLevel3::Query),
+            Some('&') => Operator::Level3(OpLevel3::QueryContinuation),
+            Some('=' | ',' | '!' | '@' | '|') => {
+                return Err(ParseError::new(
+                    ParseErrorKind::ReservedOperator,
+                    global..global + 1,
+                ));
+            }
+            _ => return Ok((0, None)),
+        };
+
+        Ok((1, Some(operator)))
     }
 }
 
@@ -378,30 +620,32 @@ impl<'t> TryParse<'t> for Option<Modifier> {
                     state.next = ModifierNext::TrailingDigit;
                 }
                 ModifierNext::LeadingDigit => {
-                    return Err(ParseError::UnexpectedInput {
-                        position: global + state.position,
-                        message: "unexpected input while parsing prefix modifier - invalid character".into(),
-                        expected: "leading integer 1-9 (see: https://datatracker.ietf.org/doc/html/rfc6570#section-2.4.1)".into(),
-                    });
+                    return Err(ParseError::new(
+                        ParseErrorKind::InvalidPrefix,
+                        char_span(rest, global + state.position),
+                    ));
                 }
                 ModifierNext::TrailingDigit if rest.starts_with(is_digit) => {
-                    if state.position < 4 {
-                        state.position += 1;
-                    } else {
-                        return Err(ParseError::UnexpectedInput {
-                            position: global + state.position,
-                            message: "unexpected input while parsing prefix modifier - out of range".into(),
-                            expected: "positive integer < 10000 (see: https://datatracker.ietf.org/doc/html/rfc6570#section-2.4.1)".into(),
-                        });
-                    }
+                    state.position += 1;
                 }
                 ModifierNext::TrailingDigit => {
-                    return Ok((
-                        state.position,
-                        Some(Modifier::Prefix(
-                            raw[1..state.position].parse::<usize>().unwrap(),
-                        )),
-                    ));
+                    // Where the prefix is out of range (including where it is
+                    // too large to be represented), the error spans all of the
+                    // digits.
+
+                    return raw[1..state.position]
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|length| PREFIX_RANGE.contains(length))
+                        .map_or_else(
+                            || {
+                                Err(ParseError::new(
+                                    ParseErrorKind::PrefixOutOfRange,
+                                    global + 1..global + state.position,
+                                ))
+                            },
+                            |length| Ok((state.position, Some(Modifier::Prefix(length)))),
+                        );
                 }
             }
         }
@@ -449,17 +693,17 @@ const fn is_non_zero_digit(c: char) -> bool {
 impl<'t> TryParse<'t> for Literal<'t> {
     fn try_parse(raw: &'t str, global: usize) -> Result<(usize, Self), ParseError> {
         match literal_satisfier().satisfy(raw) {
-            0 => Err(ParseError::UnexpectedInput {
-                position: global,
-                message: "unexpected input parsing literal component".into(),
-                expected: "valid literal characters (see: https://datatracker.ietf.org/doc/html/rfc6570#section-2.1)".into(),
-            }),
-            n => Ok((n, Literal::new(&raw[..n]))),
+            0 => Err(ParseError::new(
+                ParseErrorKind::InvalidLiteralChar,
+                char_span(raw, global),
+            )),
+            n => Ok((n, Literal::new(&raw[..n], global..global + n))),
         }
     }
 }
 
-const fn literal_satisfier() -> impl Satisfy {
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) const fn literal_satisfier() -> impl Satisfy {
     (
         Ascii::new(is_literal_ascii),
         PercentEncoded,
@@ -513,3 +757,103 @@ const fn is_literal_unicode(c: char) -> bool {
         _ => false,
     }
 }
+
+// -----------------------------------------------------------------------------
+
+// Spans
+
+// The span of the first character of the input (or an empty span, where the
+// input is empty) at the given position.
+
+fn char_span(raw: &str, global: usize) -> Range<usize> {
+    global..global + raw.chars().next().map_or(0, char::len_utf8)
+}
+
+// -----------------------------------------------------------------------------
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn errors(raw: &str) -> Vec<(ParseErrorKind, Range<usize>)> {
+        Template::parse_all(raw)
+            .unwrap_err()
+            .iter()
+            .map(|err| (err.kind(), err.span()))
+            .collect()
+    }
+
+    #[test]
+    fn error_kinds() {
+        use ParseErrorKind::*;
+
+        assert_eq!(vec![(UnclosedExpression, 0..2)], errors("{a"));
+        assert_eq!(vec![(UnclosedExpression, 1..3)], errors("/{?"));
+        assert_eq!(vec![(EmptyVarname, 1..1)], errors("{}"));
+        assert_eq!(vec![(EmptyVarname, 3..3)], errors("{a,}"));
+        assert_eq!(vec![(InvalidVarnameChar, 2..3)], errors("{a-b}"));
+        assert_eq!(vec![(InvalidPrefix, 5..6)], errors("{x.y:0}"));
+        assert_eq!(vec![(PrefixOutOfRange, 5..10)], errors("{x.y:10000}"));
+        assert_eq!(vec![(InvalidLiteralChar, 1..2)], errors("/ /"));
+        assert_eq!(vec![(ReservedOperator, 1..2)], errors("{=a}"));
+        assert_eq!(vec![(ReservedOperator, 1..2)], errors("{,a}"));
+        assert_eq!(vec![(InvalidLiteralChar, 1..2)], errors("/%zz"));
+    }
+
+    #[test]
+    fn prefix_range() {
+        assert!(Template::parse("{x.y:1}").is_ok());
+        assert!(Template::parse("{x.y:9999}").is_ok());
+        assert!(Template::parse("{x.y:10000}").is_err());
+    }
+
+    #[test]
+    fn error_recovery() {
+        use ParseErrorKind::*;
+
+        assert_eq!(
+            vec![(UnclosedExpression, 0..2), (InvalidVarnameChar, 4..5)],
+            errors("{a{b c}")
+        );
+        assert_eq!(
+            vec![(EmptyVarname, 1..1), (InvalidLiteralChar, 4..5)],
+            errors("{}/x {a}")
+        );
+        assert_eq!(
+            vec![
+                (InvalidLiteralChar, 0..1),
+                (InvalidLiteralChar, 1..2),
+                (InvalidLiteralChar, 2..3),
+            ],
+            errors("}}}")
+        );
+    }
+
+    #[test]
+    fn recovered_equivalence() {
+        let raw = "/a%2fb/{c}{?d*,e:3}#f";
+
+        assert_eq!(
+            Template::parse(raw).unwrap(),
+            Template::parse_all(raw).unwrap()
+        );
+        assert_eq!(
+            vec![Template::parse("/{a b}").unwrap_err()],
+            Template::parse_all("/{a b}").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn error_rendering() {
+        let raw = "/{a}/{b.c:0}";
+        let err = Template::parse(raw).unwrap_err();
+
+        assert_eq!(
+            "/{a}/{b.c:0}\n          ^ invalid prefix modifier (expected an integer from 1 to \
+             9999)",
+            err.render(raw)
+        );
+    }
+}
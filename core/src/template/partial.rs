@@ -0,0 +1,223 @@
+use crate::{
+    template::{
+        build::TemplateBuilder,
+        expand::{
+            Expand,
+            ExpandError,
+            ExpandOptions,
+        },
+        Component,
+        Expression,
+        OpLevel3,
+        Operator,
+        Template,
+        VariableSpecification,
+    },
+    value::source::VariableSource,
+};
+
+// =============================================================================
+// Partial
+// =============================================================================
+
+// Functions
+
+/// Expands the given components using the given [`VariableSource`], where
+/// only variables present in the source are expanded. Expressions referencing
+/// variables not present are left in place (rewritten where required) such
+/// that expansion of the resulting template with the remaining values is
+/// equivalent to expansion of the original template with all values.
+pub fn expand_partial(
+    components: &[Component<'_>],
+    values: &impl VariableSource,
+) -> Result<Template<'static>, ExpandError> {
+    let builder = components.iter().try_fold(
+        TemplateBuilder::default(),
+        |builder, component| match component {
+            Component::Expression(expression) => expression.expand_partial(values, builder),
+            Component::Literal(literal) => Ok(builder.literal(literal.value())),
+        },
+    )?;
+
+    // The builder is only given literals produced by expansion (which are
+    // always valid literals) and variables from an existing valid template, so
+    // building will not fail.
+
+    Ok(builder
+        .build()
+        .unwrap_or_else(|_| unreachable!("partial expansion produced an invalid template")))
+}
+
+// =============================================================================
+// Partial - Implementations
+// =============================================================================
+
+// Expression
+
+impl Expression<'_> {
+    fn expand_partial(
+        &self,
+        values: &impl VariableSource,
+        builder: TemplateBuilder,
+    ) -> Result<TemplateBuilder, ExpandError> {
+        let present = |variable: &VariableSpecification<'_>| values.get(variable.name().name());
+        let position = self
+            .variable_list
+            .iter()
+            .position(|variable| present(variable).is_none())
+            .unwrap_or(self.variable_list.len());
+
+        let (known, unknown) = self.variable_list.split_at(position);
+
+        // Variables present after the first unknown variable can only be
+        // expanded if they are undefined (and so contribute nothing to the
+        // expansion) - otherwise their expansion would depend on whether the
+        // preceding unknown variables are defined.
+
+        let mut remaining = Vec::with_capacity(unknown.len());
+
+        for variable in unknown {
+            match present(variable) {
+                Some(value) if value.defined() => {
+                    return Err(ExpandError::Unrepresentable { span: self.span() });
+                }
+                Some(_) => {}
+                None => remaining.push(variable),
+            }
+        }
+
+        let mut expanded = String::default();
+
+        Self::new(self.operator, known.to_vec(), self.span()).expand(
+            values,
+            &ExpandOptions::default(),
+            &mut expanded,
+        )?;
+
+        let builder = builder.literal(expanded);
+
+        if remaining.is_empty() {
+            return Ok(builder);
+        }
+
+        // Where a known variable was defined, the first string for the
+        // expression has already been expanded, so the remaining variables must
+        // be expanded as a continuation of the expression (beginning with the
+        // separator string), which is only possible for some operators.
+
+        let started = known
+            .iter()
+            .any(|variable| present(variable).map_or(false, |value| value.defined()));
+
+        let operator = if started {
+            self.operator
+                .and_then(Operator::continuation)
+                .map(Some)
+                .ok_or_else(|| ExpandError::Unrepresentable { span: self.span() })?
+        } else {
+            self.operator
+        };
+
+        Ok(builder.expression(
+            operator,
+            remaining
+                .into_iter()
+                .map(|variable| (variable.name().name(), variable.modifier())),
+        ))
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Operator
+
+impl Operator {
+    /// The operator (if any) which continues the expansion of an expression
+    /// with this operator, where the first string for the expression has
+    /// already been expanded.
+    const fn continuation(self) -> Option<Self> {
+        match self {
+            Self::Level3(OpLevel3::Query) => Some(Self::Level3(OpLevel3::QueryContinuation)),
+            Self::Level3(_) => Some(self),
+            Self::Level2(_) => None,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ExpandError,
+        Template,
+        Value,
+        Values,
+    };
+
+    fn partial(template: &str, values: &Values) -> Result<String, ExpandError> {
+        Template::parse(template)
+            .unwrap()
+            .expand_partial(values)
+            .map(|template| template.to_string())
+    }
+
+    #[test]
+    fn unknown_expression_retention() {
+        let values = Values::default().add("host", Value::item("example.com"));
+
+        assert_eq!(
+            "https://example.com/{version}{?q}",
+            partial("https://{host}/{version}{?q}", &values).unwrap()
+        );
+    }
+
+    #[test]
+    fn expression_continuation() {
+        let values = Values::default()
+            .add("a", Value::item("1"))
+            .add("c", Value::Undefined);
+
+        assert_eq!("?a=1{&b}", partial("{?a,b}", &values).unwrap());
+        assert_eq!("/1{/b}", partial("{/a,b,c}", &values).unwrap());
+        assert_eq!(";a=1{;b}", partial("{;a,b}", &values).unwrap());
+        assert_eq!("{?b}", partial("{?c,b}", &values).unwrap());
+        assert_eq!("{?b}", partial("{?b,c}", &values).unwrap());
+    }
+
+    #[test]
+    fn unrepresentable_expansion() {
+        let values = Values::default().add("a", Value::item("1"));
+
+        assert!(matches!(
+            partial("/{b,a}", &values),
+            Err(ExpandError::Unrepresentable { span }) if span == (1..6)
+        ));
+        assert!(partial("{a,b}", &values).is_err());
+        assert!(partial("{+a,b}", &values).is_err());
+        assert!(partial("{#a,b}", &values).is_err());
+    }
+
+    #[test]
+    fn staged_expansion() {
+        let template = Template::parse("{scheme}://{host}{/path*}{?q,page}").unwrap();
+        let first = Values::default()
+            .add("scheme", Value::item("https"))
+            .add("host", Value::item("example.com"))
+            .add("path", Value::list(["a b", "c"]))
+            .add("q", Value::item("rust"));
+        let second = Values::default().add("page", Value::item("2"));
+        let all = first.clone().add("page", Value::item("2"));
+
+        assert_eq!(
+            template.expand(&all).unwrap(),
+            template
+                .expand_partial(&first)
+                .unwrap()
+                .expand(&second)
+                .unwrap()
+        );
+    }
+}
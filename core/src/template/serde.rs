@@ -0,0 +1,96 @@
+use std::fmt::{
+    self,
+    Formatter,
+};
+
+use serde::{
+    de::{
+        self,
+        Visitor,
+    },
+    Deserialize,
+    Deserializer,
+    Serialize,
+    Serializer,
+};
+
+use crate::template::Template;
+
+// =============================================================================
+// Serde
+// =============================================================================
+
+// Template
+
+// Templates are serialized as their template text (as given by Display), and
+// deserialized by parsing a string, so that templates may be stored in
+// configuration (JSON, YAML, etc.) in their usual form. Deserialized templates
+// are owned, as deserializers may not be able to supply borrowed strings.
+
+impl Serialize for Template<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Template<'_> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(TemplateVisitor)
+    }
+}
+
+struct TemplateVisitor;
+
+impl Visitor<'_> for TemplateVisitor {
+    type Value = Template<'static>;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("a URI template string")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Template::parse(v)
+            .map(Template::into_owned)
+            .map_err(E::custom)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use serde::de::{
+        value::{
+            Error,
+            StrDeserializer,
+        },
+        IntoDeserializer,
+    };
+
+    use super::*;
+
+    #[test]
+    fn template_deserialization() {
+        let deserializer: StrDeserializer<'_, Error> = "/users/{id}{?q}".into_deserializer();
+
+        assert_eq!(
+            Template::parse("/users/{id}{?q}").unwrap(),
+            Template::deserialize(deserializer).unwrap()
+        );
+
+        let deserializer: StrDeserializer<'_, Error> = "/users/{id".into_deserializer();
+
+        assert!(Template::deserialize(deserializer).is_err());
+    }
+}
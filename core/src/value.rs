@@ -1,6 +1,29 @@
-use std::collections::HashMap;
+pub mod convert;
+pub mod reference;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "serde")]
+pub mod serializer;
+pub mod source;
+
+use std::{
+    collections::{
+        hash_map::{
+            Entry,
+            IntoIter,
+        },
+        HashMap,
+    },
+    fmt::Display,
+};
 
 use fnv::FnvBuildHasher;
+use thiserror::Error;
+
+use crate::value::source::{
+    Layered,
+    VariableSource,
+};
 
 // =============================================================================
 // Value
@@ -19,9 +42,9 @@ pub struct Values {
 impl Values {
     /// Adds a new [`Value`] to the [`Values`] collection and returns the
     /// modified collection to allow for chaining of calls during
-    /// construction. Values may be any type which implements `Into<Value>`
-    /// - this will generally be a concrete [`Value`] but may be your own
-    /// type for which this trait has been implemented.
+    /// construction. Values may be any type which implements `Into<Value>`;
+    /// this will generally be a concrete [`Value`] but may be your own type
+    /// for which this trait has been implemented.
     ///
     /// For clarity, it may be better to implement a suitable iterator trait for
     /// your custom type and pass it to the relevant [`Value`] construction
@@ -39,6 +62,136 @@ impl Values {
     pub fn get(&self, key: &str) -> Option<&Value> {
         self.values.get(key)
     }
+
+    /// Returns `true` if the [`Values`] collection contains no values.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// An iterator over the keys and values of the [`Values`] collection, in
+    /// arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.values.iter().map(|(key, value)| (key.as_str(), value))
+    }
+
+    /// The number of values in the [`Values`] collection.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Merges another [`Values`] collection into the [`Values`] collection.
+    /// Where both collections contain a value for the same key, and the values
+    /// differ, the given [`Conflict`] policy determines the result (unlike
+    /// [`Extend`], which always replaces existing values).
+    ///
+    /// # Errors
+    ///
+    /// This function will fail with [`MergeError::Conflict`] if the collections
+    /// contain differing values for the same key, and the policy is
+    /// [`Conflict::Error`]. Conflicts are found before any value is merged, so
+    /// the collection is unchanged on failure. Where more than one key
+    /// conflicts, the error reports the smallest conflicting key (in the
+    /// ordering of strings), so the error does not depend on iteration order.
+    ///
+    /// ```
+    /// # use uri_template_system_core::{ Conflict, Value, Values };
+    /// #
+    /// let mut values = Values::default()
+    ///     .add("host", Value::item("example.com"))
+    ///     .add("version", Value::item("v1"));
+    /// let request = Values::default()
+    ///     .add("version", Value::item("v2"))
+    ///     .add("id", Value::item("1"));
+    ///
+    /// assert!(values.merge(request.clone(), Conflict::Error).is_err());
+    /// assert_eq!(2, values.len());
+    ///
+    /// values.merge(request.clone(), Conflict::Keep).unwrap();
+    /// assert_eq!(Some(&Value::item("v1")), values.get("version"));
+    ///
+    /// values.merge(request, Conflict::Replace).unwrap();
+    /// assert_eq!(Some(&Value::item("v2")), values.get("version"));
+    /// ```
+    pub fn merge(&mut self, other: Self, conflict: Conflict) -> Result<(), MergeError> {
+        if conflict == Conflict::Error {
+            let conflicting = other
+                .values
+                .iter()
+                .filter(|(key, value)| {
+                    self.values
+                        .get(key.as_str())
+                        .map_or(false, |existing| existing != *value)
+                })
+                .map(|(key, _)| key)
+                .min();
+
+            if let Some(key) = conflicting {
+                return Err(MergeError::Conflict { name: key.clone() });
+            }
+        }
+
+        for (key, value) in other {
+            match self.values.entry(key) {
+                Entry::Occupied(mut entry) if conflict == Conflict::Replace => {
+                    entry.insert(value);
+                }
+                Entry::Occupied(_) => {}
+                Entry::Vacant(entry) => {
+                    entry.insert(value);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes the [`Value`] at the given key from the [`Values`] collection,
+    /// returning the value if it existed.
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        self.values.remove(key)
+    }
+
+    /// Layers the [`Values`] collection over a fallback [`VariableSource`],
+    /// returning a [`Layered`] source which looks up values in the collection
+    /// first, and then in the fallback. Neither the collection nor the
+    /// fallback is cloned.
+    ///
+    /// ```
+    /// # use uri_template_system_core::{ Template, Value, Values };
+    /// #
+    /// let base = Values::default()
+    ///     .add("host", Value::item("example.com"))
+    ///     .add("version", Value::item("v1"));
+    /// let request = Values::default()
+    ///     .add("version", Value::item("v2"))
+    ///     .add("id", Value::item("1"));
+    ///
+    /// let template = Template::parse("https://{host}/{version}/users/{id}").unwrap();
+    /// let source = request.with_fallback(&base);
+    ///
+    /// assert_eq!(
+    ///     "https://example.com/v2/users/1",
+    ///     template.expand(&source).unwrap()
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn with_fallback<'v, S>(&'v self, fallback: &'v S) -> Layered<'v, S>
+    where
+        S: VariableSource + ?Sized,
+    {
+        Layered::new(self, fallback)
+    }
+}
+
+/// Extending a [`Values`] collection always replaces any existing value for a
+/// key. Use [`Values::merge`] where conflicting values should instead be kept
+/// or reported (see [`Conflict`]).
+impl Extend<(String, Value)> for Values {
+    fn extend<T: IntoIterator<Item = (String, Value)>>(&mut self, iter: T) {
+        self.values.extend(iter);
+    }
 }
 
 impl FromIterator<(String, Value)> for Values {
@@ -49,14 +202,57 @@ impl FromIterator<(String, Value)> for Values {
     }
 }
 
+impl IntoIterator for Values {
+    type IntoIter = IntoIter<String, Value>;
+    type Item = (String, Value);
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.into_iter()
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Merging
+
+/// The [`Conflict`] type determines the behaviour of [`Values::merge`] when
+/// both collections contain differing values for the same key.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Conflict {
+    /// Merging fails with [`MergeError::Conflict`].
+    #[default]
+    Error,
+    /// The existing value is kept.
+    Keep,
+    /// The existing value is replaced by the merged value.
+    Replace,
+}
+
+/// An [`Error`](std::error::Error) compatible type which may be the result of a
+/// failure of [`Values::merge`].
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Error)]
+pub enum MergeError {
+    /// Both collections contain differing values for the same key, when merging
+    /// with [`Conflict::Error`].
+    #[error("conflicting values for variable \"{name}\"")]
+    Conflict {
+        /// The key with conflicting values (the smallest, where more than one
+        /// key conflicts).
+        name: String,
+    },
+}
+
 // -----------------------------------------------------------------------------
 
 // Value
 
 /// The [`Value`] type is used as the source of content during template
-/// expansion, as part of a [`Values`] collection. It maps to the three valid
-/// shapes of data defined by the [RFC](https://datatracker.ietf.org/doc/html/rfc6570) (a single item,
-/// a list of items, or a list of key/value pairs).
+/// expansion, as part of a [`Values`] collection.
+///
+/// It maps to the three valid shapes of data defined by the
+/// [RFC](https://datatracker.ietf.org/doc/html/rfc6570) (a single item, a list
+/// of items, or a list of key/value pairs).
 ///
 /// All values are of type [String] for simplicity of ownership, etc.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -114,6 +310,37 @@ impl Value {
         )
     }
 
+    /// Constructs a new [`Value::Item`] from any type which implements
+    /// [`Display`], using the displayed form of the value as the item.
+    ///
+    /// This is also the canonical formatting used when converting numbers,
+    /// booleans, and characters to values using [`From`] - integers are
+    /// formatted in decimal without separators, floats are formatted without a
+    /// trailing `.0` when integral (and never in exponent form), and booleans
+    /// are formatted as lowercase `true` and `false`. Non-finite floats are
+    /// not rejected, and are formatted as `NaN`, `inf`, and `-inf`.
+    ///
+    /// ```
+    /// # use uri_template_system_core::{ Template, Value, Values };
+    /// #
+    /// assert_eq!(Value::item("2"), Value::display(2.0));
+    /// assert_eq!(Value::item("false"), Value::display(false));
+    ///
+    /// let values = Values::default()
+    ///     .add("id", 1024)
+    ///     .add("page", 2)
+    ///     .add("draft", true);
+    /// let template = Template::parse("/posts/{id}{?page,draft}").unwrap();
+    ///
+    /// assert_eq!(
+    ///     "/posts/1024?page=2&draft=true",
+    ///     template.expand(&values).unwrap()
+    /// );
+    /// ```
+    pub fn display(value: impl Display) -> Self {
+        Self::Item(value.to_string())
+    }
+
     /// Constructs a new [`Value`] from any type which implements
     /// `Into<String>`.
     ///
@@ -158,18 +385,31 @@ impl Value {
     }
 }
 
-impl Value {
-    #[must_use]
-    pub(crate) fn defined(&self) -> bool {
-        match self {
-            Self::AssociativeArray(value) if value.is_empty() => false,
-            Self::List(value) if value.is_empty() => false,
-            Self::Undefined => false,
-            _ => true,
-        }
-    }
+// -----------------------------------------------------------------------------
+
+// From
+
+// Scalar types convert to items using the canonical formatting described for
+// `Value::display` (their `Display` implementations), so that numbers,
+// booleans, and characters can be given directly when building values.
+
+macro_rules! from_display {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Value {
+                fn from(value: $t) -> Self {
+                    Self::display(value)
+                }
+            }
+        )*
+    };
 }
 
+from_display!(i8, i16, i32, i64, i128, isize);
+from_display!(u8, u16, u32, u64, u128, usize);
+from_display!(f32, f64);
+from_display!(bool, char);
+
 // -----------------------------------------------------------------------------
 
 // Tests
@@ -204,6 +444,95 @@ mod tests {
         assert_eq!(expected, Value::item(string));
     }
 
+    #[test]
+    fn value_from_scalar() {
+        assert_eq!(Value::item("42"), Value::from(42_u32));
+        assert_eq!(Value::item("-7"), Value::from(-7_i64));
+        assert_eq!(Value::item("3"), Value::from(3.0_f64));
+        assert_eq!(Value::item("0.1"), Value::from(0.1_f32));
+        assert_eq!(Value::item("-2.5"), Value::from(-2.5_f64));
+        assert_eq!(Value::item("1000000000000000000000"), Value::from(1e21_f64));
+        assert_eq!(Value::item("0.000001"), Value::from(1e-6_f64));
+        assert_eq!(Value::item("NaN"), Value::from(f64::NAN));
+        assert_eq!(Value::item("-inf"), Value::from(f64::NEG_INFINITY));
+        assert_eq!(Value::item("true"), Value::from(true));
+        assert_eq!(Value::item("x"), Value::from('x'));
+    }
+
+    #[test]
+    fn values_merge() {
+        let base = Values::default()
+            .add("a", Value::item("1"))
+            .add("b", Value::item("2"));
+        let other = Values::default()
+            .add("b", Value::item("3"))
+            .add("c", Value::item("4"));
+
+        let mut merged = base.clone();
+        merged.merge(other.clone(), Conflict::Keep).unwrap();
+        assert_eq!(Some(&Value::item("2")), merged.get("b"));
+        assert_eq!(3, merged.len());
+
+        let mut merged = base.clone();
+        merged.merge(other.clone(), Conflict::Replace).unwrap();
+        assert_eq!(Some(&Value::item("3")), merged.get("b"));
+        assert_eq!(Some(&Value::item("4")), merged.get("c"));
+
+        let mut merged = base.clone();
+        assert!(matches!(
+            merged.merge(other, Conflict::Error),
+            Err(MergeError::Conflict { name }) if name == "b"
+        ));
+        assert_eq!(base, merged);
+        assert!(merged.merge(base, Conflict::Error).is_ok());
+    }
+
+    #[test]
+    fn values_merge_conflict_key() {
+        let keys = ["e", "b", "d", "a", "c"];
+        let mut base = keys
+            .iter()
+            .map(|key| (String::from(*key), Value::item("1")))
+            .collect::<Values>();
+        let other = keys
+            .iter()
+            .filter(|key| **key != "a")
+            .map(|key| (String::from(*key), Value::item("2")))
+            .collect::<Values>();
+
+        assert!(matches!(
+            base.merge(other, Conflict::Error),
+            Err(MergeError::Conflict { name }) if name == "b"
+        ));
+    }
+
+    #[test]
+    fn values_extend_replaces() {
+        let mut values = Values::default().add("a", Value::item("1"));
+
+        values.extend([(String::from("a"), Value::item("2"))]);
+        assert_eq!(Some(&Value::item("2")), values.get("a"));
+    }
+
+    #[test]
+    fn values_collection() {
+        let mut values = Values::default().add("a", Value::item("1"));
+
+        values.extend([(String::from("b"), Value::item("2"))]);
+        assert_eq!(2, values.len());
+
+        let mut keys = values.iter().map(|(key, _)| key).collect::<Vec<_>>();
+        keys.sort_unstable();
+        assert_eq!(vec!["a", "b"], keys);
+
+        assert_eq!(Some(Value::item("1")), values.remove("a"));
+        assert_eq!(None, values.remove("a"));
+        assert_eq!(1, values.len());
+
+        values.remove("b");
+        assert!(values.is_empty());
+    }
+
     #[test]
     fn list_value_construction() {
         let expected = Value::List(Vec::from_iter([String::from("a"), String::from("b")]));
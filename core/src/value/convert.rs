@@ -0,0 +1,274 @@
+use std::{
+    borrow::Cow,
+    collections::{
+        BTreeMap,
+        HashMap,
+    },
+    hash::BuildHasher,
+};
+
+use crate::value::{
+    reference::{
+        Items,
+        Pairs,
+        ValueRef,
+    },
+    Value,
+};
+
+// =============================================================================
+// Convert
+// =============================================================================
+
+// Traits
+
+/// The [`AsValueRef`] trait is implemented by types which have a natural
+/// representation as a [`ValueRef`], borrowing from the value where possible.
+///
+/// It is used by the code generated by `#[derive(TemplateValues)]` (when the
+/// `macros` feature of `uri-template-system` is enabled) to convert fields to
+/// values.
+///
+/// Implementations are provided for strings (as items), slices, arrays, and
+/// vecs of strings (as lists), slices, arrays, and vecs of string pairs and
+/// maps of strings (as associative arrays), [`Value`] and [`ValueRef`], and for
+/// [`Option`] of any of these (where `None` is undefined).
+///
+/// Maps are borrowed (without collecting their pairs), and so give pairs in
+/// the iteration order of the map. For a [`HashMap`] this order is arbitrary,
+/// so a [`BTreeMap`] or `IndexMap` should be preferred where the order of an
+/// expanded associative array matters (see [`Pairs`]).
+///
+/// ```
+/// # use uri_template_system_core::{ AsValueRef, ValueRef };
+/// #
+/// let tags = vec!["a", "b"];
+/// let sort: Option<String> = None;
+///
+/// assert_eq!(ValueRef::list(&tags), tags.as_value_ref());
+/// assert_eq!(ValueRef::Undefined, sort.as_value_ref());
+/// ```
+pub trait AsValueRef {
+    /// Gets the [`ValueRef`] representation of the value.
+    fn as_value_ref(&self) -> ValueRef<'_>;
+}
+
+// =============================================================================
+// Convert - Implementations
+// =============================================================================
+
+// Items
+
+impl AsValueRef for str {
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        ValueRef::item(self)
+    }
+}
+
+impl AsValueRef for String {
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        ValueRef::item(self)
+    }
+}
+
+impl AsValueRef for Cow<'_, str> {
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        ValueRef::item(self.as_ref())
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Lists
+
+impl AsValueRef for [&str] {
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        ValueRef::list(self)
+    }
+}
+
+impl AsValueRef for [String] {
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        ValueRef::list(self)
+    }
+}
+
+impl<const N: usize> AsValueRef for [&str; N] {
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        ValueRef::list(&self[..])
+    }
+}
+
+impl<const N: usize> AsValueRef for [String; N] {
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        ValueRef::list(&self[..])
+    }
+}
+
+impl AsValueRef for Vec<&str> {
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        ValueRef::list(&self[..])
+    }
+}
+
+impl AsValueRef for Vec<String> {
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        ValueRef::list(self)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Associative Arrays
+
+impl AsValueRef for [(&str, &str)] {
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        ValueRef::associative_array(self)
+    }
+}
+
+impl AsValueRef for [(String, String)] {
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        ValueRef::associative_array(self)
+    }
+}
+
+impl<const N: usize> AsValueRef for [(&str, &str); N] {
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        ValueRef::associative_array(&self[..])
+    }
+}
+
+impl<const N: usize> AsValueRef for [(String, String); N] {
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        ValueRef::associative_array(&self[..])
+    }
+}
+
+impl AsValueRef for Vec<(&str, &str)> {
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        ValueRef::associative_array(&self[..])
+    }
+}
+
+impl AsValueRef for Vec<(String, String)> {
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        ValueRef::associative_array(self)
+    }
+}
+
+impl<K, V> AsValueRef for BTreeMap<K, V>
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        ValueRef::associative_array(self)
+    }
+}
+
+impl<K, V, S> AsValueRef for HashMap<K, V, S>
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+    S: BuildHasher,
+{
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        ValueRef::associative_array(self)
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<K, V, S> AsValueRef for indexmap::IndexMap<K, V, S>
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+    S: BuildHasher,
+{
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        ValueRef::associative_array(self)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Values
+
+impl AsValueRef for Value {
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        ValueRef::from(self)
+    }
+}
+
+impl AsValueRef for ValueRef<'_> {
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        self.clone()
+    }
+}
+
+impl AsValueRef for Items<'_> {
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        ValueRef::List(self.clone())
+    }
+}
+
+impl AsValueRef for Pairs<'_> {
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        ValueRef::AssociativeArray(self.clone())
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Wrappers
+
+impl<T> AsValueRef for Option<T>
+where
+    T: AsValueRef,
+{
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        self.as_ref()
+            .map_or(ValueRef::Undefined, AsValueRef::as_value_ref)
+    }
+}
+
+impl<T> AsValueRef for &T
+where
+    T: AsValueRef + ?Sized,
+{
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        (**self).as_value_ref()
+    }
+}
+
+impl<T> AsValueRef for Box<T>
+where
+    T: AsValueRef + ?Sized,
+{
+    fn as_value_ref(&self) -> ValueRef<'_> {
+        (**self).as_value_ref()
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn value_ref_conversion() {
+        let map = BTreeMap::from([("a", String::from("1"))]);
+
+        assert_eq!(ValueRef::item("a"), "a".as_value_ref());
+        assert_eq!(ValueRef::list(&["a", "b"]), ["a", "b"].as_value_ref());
+        assert_eq!(
+            ValueRef::associative_array(&[("a", "1")]),
+            map.as_value_ref()
+        );
+        assert_eq!(ValueRef::Undefined, None::<&str>.as_value_ref());
+        assert_eq!(ValueRef::item("a"), Some(Value::item("a")).as_value_ref());
+    }
+}
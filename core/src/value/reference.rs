@@ -0,0 +1,556 @@
+use std::{
+    borrow::Cow,
+    collections::{
+        BTreeMap,
+        HashMap,
+    },
+    fmt::{
+        self,
+        Debug,
+        Formatter,
+    },
+    hash::BuildHasher,
+    ops::Range,
+};
+
+use crate::value::Value;
+
+// =============================================================================
+// Reference
+// =============================================================================
+
+// Value Reference
+
+/// The [`ValueRef`] type is a (potentially) borrowed view of a value, as
+/// returned by a [`VariableSource`](crate::VariableSource), with the same
+/// shapes as [`Value`].
+///
+/// Items, lists, and associative arrays may borrow from existing data (lists
+/// and associative arrays from slices of `String` or `&str`, see [`Items`] and
+/// [`Pairs`]), so that expansion does not require data to be copied. Owned
+/// data may also be used where borrowing is not possible.
+///
+/// ```
+/// # use uri_template_system_core::{ Template, ValueRef, VariableSource };
+/// #
+/// struct Page {
+///     tags: Vec<&'static str>,
+///     number: usize,
+/// }
+///
+/// impl VariableSource for Page {
+///     fn get(&self, name: &str) -> Option<ValueRef<'_>> {
+///         match name {
+///             "tags" => Some(ValueRef::list(&self.tags)),
+///             "page" => Some(ValueRef::item(self.number.to_string())),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// let template = Template::parse("/posts{?tags,page}").unwrap();
+/// let page = Page {
+///     tags: vec!["a", "b"],
+///     number: 2,
+/// };
+///
+/// assert_eq!("/posts?tags=a,b&page=2", template.expand(&page).unwrap());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ValueRef<'a> {
+    /// An associative array value (see [`Value::AssociativeArray`]).
+    AssociativeArray(Pairs<'a>),
+    /// An item value (see [`Value::Item`]).
+    Item(Cow<'a, str>),
+    /// A list value (see [`Value::List`]).
+    List(Items<'a>),
+    /// An undefined value (see [`Value::Undefined`]).
+    Undefined,
+}
+
+impl<'a> ValueRef<'a> {
+    /// Constructs a new [`ValueRef`] from any type which implements
+    /// `Into<Pairs>`, such as a borrowed slice, array, or vec of `String` or
+    /// `&str` pairs (tuples), or an owned vec of pairs.
+    ///
+    /// ```
+    /// # use uri_template_system_core::ValueRef;
+    /// #
+    /// let pairs = [("a", "1"), ("b", "2")];
+    /// let owned = vec![
+    ///     (String::from("a"), String::from("1")),
+    ///     (String::from("b"), String::from("2")),
+    /// ];
+    ///
+    /// assert_eq!(
+    ///     ValueRef::associative_array(&pairs),
+    ///     ValueRef::associative_array(&owned)
+    /// );
+    /// ```
+    pub fn associative_array(value: impl Into<Pairs<'a>>) -> Self {
+        Self::AssociativeArray(value.into())
+    }
+
+    /// Constructs a new [`ValueRef`] from any type which implements
+    /// `Into<Cow<str>>`, such as a borrowed `&str` or an owned [`String`].
+    ///
+    /// ```
+    /// # use uri_template_system_core::ValueRef;
+    /// #
+    /// assert_eq!(ValueRef::item("a"), ValueRef::item(String::from("a")));
+    /// ```
+    pub fn item(value: impl Into<Cow<'a, str>>) -> Self {
+        Self::Item(value.into())
+    }
+
+    /// Constructs a new [`ValueRef`] from any type which implements
+    /// `Into<Items>`, such as a borrowed slice, array, or vec of `String` or
+    /// `&str` items, or an owned vec of items.
+    ///
+    /// ```
+    /// # use uri_template_system_core::ValueRef;
+    /// #
+    /// let items = ["a", "b"];
+    /// let owned = vec![String::from("a"), String::from("b")];
+    ///
+    /// assert_eq!(ValueRef::list(&items), ValueRef::list(&owned));
+    /// ```
+    pub fn list(value: impl Into<Items<'a>>) -> Self {
+        Self::List(value.into())
+    }
+}
+
+impl ValueRef<'_> {
+    #[must_use]
+    pub(crate) fn defined(&self) -> bool {
+        match self {
+            Self::AssociativeArray(value) => !value.is_empty(),
+            Self::List(value) => !value.is_empty(),
+            Self::Item(_) => true,
+            Self::Undefined => false,
+        }
+    }
+}
+
+impl<'a> From<&'a Value> for ValueRef<'a> {
+    fn from(value: &'a Value) -> Self {
+        match value {
+            Value::AssociativeArray(value) => Self::associative_array(value),
+            Value::Item(value) => Self::item(value),
+            Value::List(value) => Self::list(value),
+            Value::Undefined => Self::Undefined,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Items
+
+/// The [`Items`] type is the (potentially) borrowed list of items of a
+/// [`ValueRef::List`], which may borrow a slice of `String` or `&str` items,
+/// or own a vec of items.
+#[derive(Clone)]
+pub struct Items<'a> {
+    items: ItemsRepr<'a>,
+}
+
+#[derive(Clone)]
+enum ItemsRepr<'a> {
+    Owned(Vec<Cow<'a, str>>),
+    Str(&'a [&'a str]),
+    String(&'a [String]),
+}
+
+impl Items<'_> {
+    /// Returns an iterator over the items.
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+        (0..self.len()).map(move |i| match &self.items {
+            ItemsRepr::Owned(items) => &items[i],
+            ItemsRepr::Str(items) => items[i],
+            ItemsRepr::String(items) => &items[i],
+        })
+    }
+
+    /// Returns `true` if there are no items.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of items.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        match &self.items {
+            ItemsRepr::Owned(items) => items.len(),
+            ItemsRepr::Str(items) => items.len(),
+            ItemsRepr::String(items) => items.len(),
+        }
+    }
+}
+
+impl Debug for Items<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Eq for Items<'_> {}
+
+impl PartialEq for Items<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<'a> From<&'a [&'a str]> for Items<'a> {
+    fn from(items: &'a [&'a str]) -> Self {
+        Self {
+            items: ItemsRepr::Str(items),
+        }
+    }
+}
+
+impl<'a> From<&'a [String]> for Items<'a> {
+    fn from(items: &'a [String]) -> Self {
+        Self {
+            items: ItemsRepr::String(items),
+        }
+    }
+}
+
+impl<'a, const N: usize> From<&'a [&'a str; N]> for Items<'a> {
+    fn from(items: &'a [&'a str; N]) -> Self {
+        Self::from(&items[..])
+    }
+}
+
+impl<'a, const N: usize> From<&'a [String; N]> for Items<'a> {
+    fn from(items: &'a [String; N]) -> Self {
+        Self::from(&items[..])
+    }
+}
+
+impl<'a> From<&'a Vec<&'a str>> for Items<'a> {
+    fn from(items: &'a Vec<&'a str>) -> Self {
+        Self::from(&items[..])
+    }
+}
+
+impl<'a> From<&'a Vec<String>> for Items<'a> {
+    fn from(items: &'a Vec<String>) -> Self {
+        Self::from(&items[..])
+    }
+}
+
+impl<'a, T> From<Vec<T>> for Items<'a>
+where
+    T: Into<Cow<'a, str>>,
+{
+    fn from(items: Vec<T>) -> Self {
+        Self {
+            items: ItemsRepr::Owned(items.into_iter().map(Into::into).collect()),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Pairs
+
+/// The [`Pairs`] type is the (potentially) borrowed list of pairs of a
+/// [`ValueRef::AssociativeArray`], which may borrow a slice or map of pairs,
+/// or own a vec of pairs.
+///
+/// Pairs borrowed from a map are given in the iteration order of the map,
+/// which for a [`HashMap`] is arbitrary, and may differ between runs. Where the
+/// order of pairs in an expansion matters, a [`BTreeMap`] (ordered by key) or
+/// an `IndexMap` (ordered by insertion, when the `indexmap` feature is enabled)
+/// should be used instead.
+#[derive(Clone)]
+pub struct Pairs<'a> {
+    pairs: PairsRepr<'a>,
+}
+
+#[derive(Clone)]
+enum PairsRepr<'a> {
+    Map(&'a dyn PairsMap),
+    Owned(Vec<(Cow<'a, str>, Cow<'a, str>)>),
+    Str(&'a [(&'a str, &'a str)]),
+    String(&'a [(String, String)]),
+}
+
+impl Pairs<'_> {
+    /// Returns an iterator over the pairs.
+    ///
+    /// Iterating over pairs borrowed from a map requires a (single) allocation,
+    /// which expansion avoids.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        match &self.pairs {
+            PairsRepr::Map(map) => PairsIter::Map(map.iter()),
+            _ => PairsIter::Indexed(self, 0..self.len()),
+        }
+    }
+
+    /// Returns `true` if there are no pairs.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of pairs.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        match &self.pairs {
+            PairsRepr::Map(map) => map.len(),
+            PairsRepr::Owned(pairs) => pairs.len(),
+            PairsRepr::Str(pairs) => pairs.len(),
+            PairsRepr::String(pairs) => pairs.len(),
+        }
+    }
+
+    /// Calls the given function for each pair in turn, stopping at the first
+    /// error. Unlike [`Pairs::iter`], this does not allocate for pairs
+    /// borrowed from a map.
+    pub(crate) fn try_for_each(&self, mut f: impl FnMut(&str, &str) -> fmt::Result) -> fmt::Result {
+        match &self.pairs {
+            PairsRepr::Map(map) => map.try_for_each(&mut f),
+            _ => self.iter().try_for_each(|(name, value)| f(name, value)),
+        }
+    }
+
+    fn get(&self, i: usize) -> Option<(&str, &str)> {
+        match &self.pairs {
+            PairsRepr::Map(_) => None,
+            PairsRepr::Owned(pairs) => pairs.get(i).map(|(k, v)| (&**k, &**v)),
+            PairsRepr::Str(pairs) => pairs.get(i).copied(),
+            PairsRepr::String(pairs) => pairs.get(i).map(|(k, v)| (&**k, &**v)),
+        }
+    }
+}
+
+enum PairsIter<'p> {
+    Indexed(&'p Pairs<'p>, Range<usize>),
+    Map(Box<dyn Iterator<Item = (&'p str, &'p str)> + 'p>),
+}
+
+impl<'p> Iterator for PairsIter<'p> {
+    type Item = (&'p str, &'p str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Indexed(pairs, range) => range.next().and_then(|i| pairs.get(i)),
+            Self::Map(iter) => iter.next(),
+        }
+    }
+}
+
+impl Debug for Pairs<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Eq for Pairs<'_> {}
+
+impl PartialEq for Pairs<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<'a> From<&'a [(&'a str, &'a str)]> for Pairs<'a> {
+    fn from(pairs: &'a [(&'a str, &'a str)]) -> Self {
+        Self {
+            pairs: PairsRepr::Str(pairs),
+        }
+    }
+}
+
+impl<'a> From<&'a [(String, String)]> for Pairs<'a> {
+    fn from(pairs: &'a [(String, String)]) -> Self {
+        Self {
+            pairs: PairsRepr::String(pairs),
+        }
+    }
+}
+
+impl<'a, const N: usize> From<&'a [(&'a str, &'a str); N]> for Pairs<'a> {
+    fn from(pairs: &'a [(&'a str, &'a str); N]) -> Self {
+        Self::from(&pairs[..])
+    }
+}
+
+impl<'a, const N: usize> From<&'a [(String, String); N]> for Pairs<'a> {
+    fn from(pairs: &'a [(String, String); N]) -> Self {
+        Self::from(&pairs[..])
+    }
+}
+
+impl<'a> From<&'a Vec<(&'a str, &'a str)>> for Pairs<'a> {
+    fn from(pairs: &'a Vec<(&'a str, &'a str)>) -> Self {
+        Self::from(&pairs[..])
+    }
+}
+
+impl<'a> From<&'a Vec<(String, String)>> for Pairs<'a> {
+    fn from(pairs: &'a Vec<(String, String)>) -> Self {
+        Self::from(&pairs[..])
+    }
+}
+
+impl<'a, T, U> From<Vec<(T, U)>> for Pairs<'a>
+where
+    T: Into<Cow<'a, str>>,
+    U: Into<Cow<'a, str>>,
+{
+    fn from(pairs: Vec<(T, U)>) -> Self {
+        Self {
+            pairs: PairsRepr::Owned(
+                pairs
+                    .into_iter()
+                    .map(|(t, u)| (t.into(), u.into()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl<'a, K, V> From<&'a BTreeMap<K, V>> for Pairs<'a>
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    fn from(pairs: &'a BTreeMap<K, V>) -> Self {
+        Self {
+            pairs: PairsRepr::Map(pairs),
+        }
+    }
+}
+
+impl<'a, K, V, S> From<&'a HashMap<K, V, S>> for Pairs<'a>
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+    S: BuildHasher,
+{
+    fn from(pairs: &'a HashMap<K, V, S>) -> Self {
+        Self {
+            pairs: PairsRepr::Map(pairs),
+        }
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<'a, K, V, S> From<&'a indexmap::IndexMap<K, V, S>> for Pairs<'a>
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+    S: BuildHasher,
+{
+    fn from(pairs: &'a indexmap::IndexMap<K, V, S>) -> Self {
+        Self {
+            pairs: PairsRepr::Map(pairs),
+        }
+    }
+}
+
+// Maps
+
+// Maps are borrowed as trait objects, so that pairs may borrow any map of
+// string-like keys and values without collecting the pairs of the map.
+
+trait PairsMap {
+    fn iter(&self) -> Box<dyn Iterator<Item = (&str, &str)> + '_>;
+    fn len(&self) -> usize;
+    fn try_for_each(&self, f: &mut dyn FnMut(&str, &str) -> fmt::Result) -> fmt::Result;
+}
+
+macro_rules! pairs_map {
+    ($t:ty, $($g:tt)*) => {
+        impl<$($g)*> PairsMap for $t
+        where
+            K: AsRef<str>,
+            V: AsRef<str>,
+        {
+            fn iter(&self) -> Box<dyn Iterator<Item = (&str, &str)> + '_> {
+                Box::new(Self::iter(self).map(|(k, v)| (k.as_ref(), v.as_ref())))
+            }
+
+            fn len(&self) -> usize {
+                Self::len(self)
+            }
+
+            fn try_for_each(&self, f: &mut dyn FnMut(&str, &str) -> fmt::Result) -> fmt::Result {
+                Self::iter(self).try_for_each(|(k, v)| f(k.as_ref(), v.as_ref()))
+            }
+        }
+    };
+}
+
+pairs_map!(BTreeMap<K, V>, K, V);
+pairs_map!(HashMap<K, V, S>, K, V, S);
+#[cfg(feature = "indexmap")]
+pairs_map!(indexmap::IndexMap<K, V, S>, K, V, S);
+
+// -----------------------------------------------------------------------------
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn items_equivalence() {
+        let strs = ["a", "b"];
+        let strings = vec![String::from("a"), String::from("b")];
+        let cows = vec![Cow::Borrowed("a"), Cow::Owned(String::from("b"))];
+
+        assert_eq!(Items::from(&strs), Items::from(&strings));
+        assert_eq!(Items::from(&strings), Items::from(cows));
+        assert_eq!(
+            vec!["a", "b"],
+            Items::from(&strs).iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn pairs_equivalence() {
+        let strs = [("a", "1"), ("b", "2")];
+        let strings = vec![
+            (String::from("a"), String::from("1")),
+            (String::from("b"), String::from("2")),
+        ];
+
+        assert_eq!(Pairs::from(&strs), Pairs::from(&strings));
+        assert_eq!(Pairs::from(&strings), Pairs::from(strs.to_vec()));
+        assert_eq!(2, Pairs::from(&strs).len());
+    }
+
+    #[test]
+    fn map_pairs_equivalence() {
+        let strs = [("a", "1"), ("b", "2")];
+        let map = BTreeMap::from(strs);
+        let pairs = Pairs::from(&map);
+        let mut visited = Vec::new();
+
+        pairs
+            .try_for_each(|name, value| {
+                visited.push(format!("{name}={value}"));
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(Pairs::from(&strs), pairs);
+        assert_eq!(vec!["a=1", "b=2"], visited);
+    }
+
+    #[test]
+    fn value_conversion() {
+        let value = Value::list(["a", "b"]);
+
+        assert_eq!(ValueRef::list(&["a", "b"]), ValueRef::from(&value));
+        assert!(!ValueRef::from(&Value::list(Vec::<String>::new())).defined());
+    }
+}
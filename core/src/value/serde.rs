@@ -0,0 +1,355 @@
+use std::fmt::{
+    self,
+    Formatter,
+};
+
+use serde::{
+    de::{
+        self,
+        MapAccess,
+        SeqAccess,
+        Visitor,
+    },
+    ser::{
+        SerializeMap,
+        SerializeSeq,
+    },
+    Deserialize,
+    Deserializer,
+    Serialize,
+    Serializer,
+};
+
+use crate::value::{
+    Value,
+    Values,
+};
+
+// =============================================================================
+// Serde
+// =============================================================================
+
+// Value
+
+// Values are represented using the JSON shape of the uritemplate-test fixtures:
+// items as strings, lists as arrays of strings, associative arrays as objects
+// with string values, and undefined values as null. Numbers and booleans are
+// also accepted when deserializing, wherever a string is (the fixtures contain
+// numbers), and are converted to strings as for the scalar conversions to
+// Value.
+
+impl Serialize for Value {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::AssociativeArray(pairs) => {
+                let mut map = serializer.serialize_map(Some(pairs.len()))?;
+
+                for (key, value) in pairs {
+                    map.serialize_entry(key, value)?;
+                }
+
+                map.end()
+            }
+            Self::Item(value) => serializer.serialize_str(value),
+            Self::List(items) => {
+                let mut seq = serializer.serialize_seq(Some(items.len()))?;
+
+                for item in items {
+                    seq.serialize_element(item)?;
+                }
+
+                seq.end()
+            }
+            Self::Undefined => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("a scalar, an array of scalars, an object of scalars, or null")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::from(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::from(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::from(v))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::from(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::Item(v.to_owned()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::Item(v))
+    }
+
+    fn visit_none<E>(self) -> Result<Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::Undefined)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_unit<E>(self) -> Result<Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::Undefined)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+
+        while let Some(Scalar(item)) = seq.next_element()? {
+            items.push(item);
+        }
+
+        Ok(Value::List(items))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut pairs = Vec::with_capacity(map.size_hint().unwrap_or_default());
+
+        while let Some((key, Scalar(value))) = map.next_entry()? {
+            pairs.push((key, value));
+        }
+
+        Ok(Value::AssociativeArray(pairs))
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Scalar
+
+// Scalars are the members of lists and the values of associative arrays, which
+// may be given as strings, numbers, or booleans.
+
+struct Scalar(String);
+
+impl<'de> Deserialize<'de> for Scalar {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ScalarVisitor)
+    }
+}
+
+struct ScalarVisitor;
+
+impl Visitor<'_> for ScalarVisitor {
+    type Value = Scalar;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("a string, number, or boolean")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Scalar, E>
+    where
+        E: de::Error,
+    {
+        Ok(Scalar(v.to_string()))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Scalar, E>
+    where
+        E: de::Error,
+    {
+        Ok(Scalar(v.to_string()))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Scalar, E>
+    where
+        E: de::Error,
+    {
+        Ok(Scalar(v.to_string()))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Scalar, E>
+    where
+        E: de::Error,
+    {
+        Ok(Scalar(v.to_string()))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Scalar, E>
+    where
+        E: de::Error,
+    {
+        Ok(Scalar(v.to_owned()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Scalar, E>
+    where
+        E: de::Error,
+    {
+        Ok(Scalar(v))
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Values
+
+// Values are represented as a map of variable names to values.
+
+impl Serialize for Values {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_map(&self.values)
+    }
+}
+
+impl<'de> Deserialize<'de> for Values {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(ValuesVisitor)
+    }
+}
+
+struct ValuesVisitor;
+
+impl<'de> Visitor<'de> for ValuesVisitor {
+    type Value = Values;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("a map of variable names to values")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Values, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut values = Vec::with_capacity(map.size_hint().unwrap_or_default());
+
+        while let Some(entry) = map.next_entry()? {
+            values.push(entry);
+        }
+
+        Ok(values.into_iter().collect())
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use serde::de::{
+        value::{
+            Error,
+            MapDeserializer,
+            SeqDeserializer,
+        },
+        IntoDeserializer,
+    };
+
+    use super::*;
+
+    #[test]
+    fn value_deserialization() {
+        let item: de::value::StrDeserializer<'_, Error> = "a".into_deserializer();
+        let number: de::value::F64Deserializer<Error> = 6.5.into_deserializer();
+        let list = SeqDeserializer::<_, Error>::new(["a", "b"].into_iter());
+        let pairs = MapDeserializer::<_, Error>::new([("a", "1"), ("b", "2")].into_iter());
+        let unit: de::value::UnitDeserializer<Error> = ().into_deserializer();
+
+        assert_eq!(Value::item("a"), Value::deserialize(item).unwrap());
+        assert_eq!(Value::item("6.5"), Value::deserialize(number).unwrap());
+        assert_eq!(Value::list(["a", "b"]), Value::deserialize(list).unwrap());
+        assert_eq!(
+            Value::associative_array([("a", "1"), ("b", "2")]),
+            Value::deserialize(pairs).unwrap()
+        );
+        assert_eq!(Value::Undefined, Value::deserialize(unit).unwrap());
+    }
+
+    #[test]
+    fn scalar_member_deserialization() {
+        let numbers = SeqDeserializer::<_, Error>::new([1_u64, 2].into_iter());
+        let floats = SeqDeserializer::<_, Error>::new([1.5_f64, -2.0].into_iter());
+        let booleans = SeqDeserializer::<_, Error>::new([true, false].into_iter());
+        let pairs = MapDeserializer::<_, Error>::new([("a", 1_i64), ("b", -2)].into_iter());
+
+        assert_eq!(
+            Value::list(["1", "2"]),
+            Value::deserialize(numbers).unwrap()
+        );
+        assert_eq!(
+            Value::list(["1.5", "-2"]),
+            Value::deserialize(floats).unwrap()
+        );
+        assert_eq!(
+            Value::list(["true", "false"]),
+            Value::deserialize(booleans).unwrap()
+        );
+        assert_eq!(
+            Value::associative_array([("a", "1"), ("b", "-2")]),
+            Value::deserialize(pairs).unwrap()
+        );
+    }
+}
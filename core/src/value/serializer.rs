@@ -0,0 +1,550 @@
+use std::fmt::Display;
+
+use serde::{
+    ser::{
+        self,
+        Impossible,
+        SerializeMap,
+        SerializeSeq,
+        SerializeStruct,
+        SerializeTuple,
+        SerializeTupleStruct,
+    },
+    Serialize,
+    Serializer,
+};
+use thiserror::Error;
+
+use crate::value::{
+    Value,
+    Values,
+};
+
+// =============================================================================
+// Serializer
+// =============================================================================
+
+impl Values {
+    /// Constructs a new [`Values`] collection from any type which implements
+    /// [`Serialize`], where the type serializes as a struct or map. Each field
+    /// (or entry) of the struct (or map) becomes a [`Value`], such that:
+    ///
+    /// - strings, characters, numbers and booleans become [`Value::Item`]
+    ///   (numbers and booleans are formatted using
+    ///   [`Display`](std::fmt::Display), e.g. `1`, `1.5`, and `true`)
+    /// - sequences (including tuples) become [`Value::List`]
+    /// - maps and structs become [`Value::AssociativeArray`]
+    /// - `None` and unit values become [`Value::Undefined`]
+    ///
+    /// Members of lists and associative arrays must be strings, characters,
+    /// numbers or booleans (members which are `None` are omitted), as the RFC
+    /// does not define nested composite values.
+    ///
+    /// This function is only available with the `serde` feature enabled.
+    ///
+    /// # Errors
+    ///
+    /// This function will fail if the given value does not serialize as a
+    /// struct or map, or if any field serializes as a type which can not be
+    /// represented as a [`Value`] (such as bytes, enum variants with data, or
+    /// nested composite values).
+    ///
+    /// ```
+    /// # use uri_template_system_core::{ Value, Values };
+    /// # use serde::Serialize;
+    /// #
+    /// #[derive(Serialize)]
+    /// struct Search {
+    ///     q: &'static str,
+    ///     page: u32,
+    ///     tags: Vec<&'static str>,
+    ///     sort: Option<&'static str>,
+    /// }
+    ///
+    /// let search = Search {
+    ///     q: "rust",
+    ///     page: 2,
+    ///     tags: vec!["a", "b"],
+    ///     sort: None,
+    /// };
+    ///
+    /// assert_eq!(
+    ///     Values::default()
+    ///         .add("q", Value::item("rust"))
+    ///         .add("page", Value::item("2"))
+    ///         .add("tags", Value::list(["a", "b"]))
+    ///         .add("sort", Value::Undefined),
+    ///     Values::from_serialize(&search).unwrap()
+    /// );
+    /// ```
+    pub fn from_serialize(value: &impl Serialize) -> Result<Self, SerializeError> {
+        match value.serialize(NodeSerializer)? {
+            Node::Map(entries) => entries
+                .into_iter()
+                .map(|(name, node)| {
+                    let name = scalar(name).ok_or(SerializeError::InvalidName)?;
+                    let value = node.into_value(&name)?;
+
+                    Ok((name, value))
+                })
+                .collect(),
+            _ => Err(SerializeError::InvalidRoot),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Errors
+
+/// An [`Error`](std::error::Error) compatible type which may be the result of a
+/// failure of [`Values::from_serialize`], where the given value can not be
+/// represented as [`Values`].
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Error)]
+pub enum SerializeError {
+    /// A custom error returned by the [`Serialize`] implementation of the
+    /// given value.
+    #[error("{0}")]
+    Custom(String),
+    /// A map key (used as a variable name) was not a string, character,
+    /// number or boolean.
+    #[error("variable names must be strings, characters, numbers or booleans")]
+    InvalidName,
+    /// The given value did not serialize as a struct or map.
+    #[error("values must be serialized from a struct or map")]
+    InvalidRoot,
+    /// A field serialized as a composite value which contained composite
+    /// values (or a map key which was not a string, character, number or
+    /// boolean), which can not be represented as a [`Value`].
+    #[error("variable \"{name}\" has a nested composite value")]
+    Nested {
+        /// The name of the variable with the nested value.
+        name: String,
+    },
+    /// A type was serialized which has no representation as a [`Value`].
+    #[error("unsupported type: {0}")]
+    Unsupported(&'static str),
+}
+
+impl ser::Error for SerializeError {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: Display,
+    {
+        Self::Custom(msg.to_string())
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Node
+
+/// An intermediate representation of serialized data, converted to values once
+/// the shape of the data is known.
+enum Node {
+    Map(Vec<(Self, Self)>),
+    None,
+    Scalar(String),
+    Seq(Vec<Self>),
+}
+
+impl Node {
+    fn into_value(self, name: &str) -> Result<Value, SerializeError> {
+        let nested = || SerializeError::Nested { name: name.into() };
+
+        match self {
+            Self::Map(entries) => entries
+                .into_iter()
+                .filter(|(_, value)| !matches!(value, Self::None))
+                .map(|(key, value)| scalar(key).zip(scalar(value)).ok_or_else(nested))
+                .collect::<Result<_, _>>()
+                .map(Value::AssociativeArray),
+            Self::None => Ok(Value::Undefined),
+            Self::Scalar(value) => Ok(Value::Item(value)),
+            Self::Seq(nodes) => nodes
+                .into_iter()
+                .filter(|node| !matches!(node, Self::None))
+                .map(|node| scalar(node).ok_or_else(nested))
+                .collect::<Result<_, _>>()
+                .map(Value::List),
+        }
+    }
+}
+
+fn scalar(node: Node) -> Option<String> {
+    match node {
+        Node::Scalar(value) => Some(value),
+        _ => None,
+    }
+}
+
+// =============================================================================
+// Serializer - Implementations
+// =============================================================================
+
+// Node Serializer
+
+struct NodeSerializer;
+
+impl NodeSerializer {
+    #[allow(clippy::unnecessary_wraps)]
+    fn scalar(value: impl Display) -> Result<Node, SerializeError> {
+        Ok(Node::Scalar(value.to_string()))
+    }
+}
+
+impl Serializer for NodeSerializer {
+    type Error = SerializeError;
+    type Ok = Node;
+    type SerializeMap = MapSerializer;
+    type SerializeSeq = SeqSerializer;
+    type SerializeStruct = MapSerializer;
+    type SerializeStructVariant = Impossible<Node, SerializeError>;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = Impossible<Node, SerializeError>;
+
+    fn serialize_bool(self, v: bool) -> Result<Node, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Node, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Node, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Node, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Node, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Node, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Node, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Node, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Node, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Node, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Node, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Node, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Node, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<Node, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Node, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<Node, SerializeError> {
+        Err(SerializeError::Unsupported("bytes"))
+    }
+
+    fn serialize_none(self) -> Result<Node, SerializeError> {
+        Ok(Node::None)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Node, SerializeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Node, SerializeError> {
+        Ok(Node::None)
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Node, SerializeError> {
+        Ok(Node::None)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Node, SerializeError> {
+        Self::scalar(variant)
+    }
+
+    fn serialize_newtype_struct<T>(self, _: &'static str, value: &T) -> Result<Node, SerializeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Node, SerializeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        Err(SerializeError::Unsupported("newtype variant"))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqSerializer, SerializeError> {
+        Ok(SeqSerializer {
+            nodes: Vec::with_capacity(len.unwrap_or_default()),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqSerializer, SerializeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        len: usize,
+    ) -> Result<SeqSerializer, SerializeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerializeError> {
+        Err(SerializeError::Unsupported("tuple variant"))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<MapSerializer, SerializeError> {
+        Ok(MapSerializer {
+            entries: Vec::with_capacity(len.unwrap_or_default()),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        len: usize,
+    ) -> Result<MapSerializer, SerializeError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, SerializeError> {
+        Err(SerializeError::Unsupported("struct variant"))
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Sequence Serializer
+
+struct SeqSerializer {
+    nodes: Vec<Node>,
+}
+
+impl SerializeSeq for SeqSerializer {
+    type Error = SerializeError;
+    type Ok = Node;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), SerializeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.nodes.push(value.serialize(NodeSerializer)?);
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<Node, SerializeError> {
+        Ok(Node::Seq(self.nodes))
+    }
+}
+
+impl SerializeTuple for SeqSerializer {
+    type Error = SerializeError;
+    type Ok = Node;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), SerializeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Node, SerializeError> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl SerializeTupleStruct for SeqSerializer {
+    type Error = SerializeError;
+    type Ok = Node;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), SerializeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Node, SerializeError> {
+        SerializeSeq::end(self)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Map Serializer
+
+struct MapSerializer {
+    entries: Vec<(Node, Node)>,
+    key: Option<Node>,
+}
+
+impl SerializeMap for MapSerializer {
+    type Error = SerializeError;
+    type Ok = Node;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), SerializeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.key = Some(key.serialize(NodeSerializer)?);
+
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), SerializeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        let key = self.key.take().unwrap_or(Node::None);
+
+        self.entries.push((key, value.serialize(NodeSerializer)?));
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<Node, SerializeError> {
+        Ok(Node::Map(self.entries))
+    }
+}
+
+impl SerializeStruct for MapSerializer {
+    type Error = SerializeError;
+    type Ok = Node;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), SerializeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.entries
+            .push((Node::Scalar(key.into()), value.serialize(NodeSerializer)?));
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<Node, SerializeError> {
+        SerializeMap::end(self)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde::Serialize;
+
+    use super::*;
+
+    #[derive(Serialize)]
+    enum Order {
+        Ascending,
+    }
+
+    #[derive(Serialize)]
+    struct Query {
+        count: u8,
+        ratio: f64,
+        enabled: bool,
+        order: Order,
+        point: (i32, i32),
+        filters: BTreeMap<&'static str, Option<&'static str>>,
+    }
+
+    #[test]
+    fn struct_serialization() {
+        let query = Query {
+            count: 3,
+            ratio: 1.5,
+            enabled: true,
+            order: Order::Ascending,
+            point: (1, -1),
+            filters: BTreeMap::from([("a", Some("x")), ("b", None)]),
+        };
+
+        assert_eq!(
+            Values::default()
+                .add("count", Value::item("3"))
+                .add("ratio", Value::item("1.5"))
+                .add("enabled", Value::item("true"))
+                .add("order", Value::item("Ascending"))
+                .add("point", Value::list(["1", "-1"]))
+                .add("filters", Value::associative_array([("a", "x")])),
+            Values::from_serialize(&query).unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_serialization() {
+        assert!(matches!(
+            Values::from_serialize(&"a"),
+            Err(SerializeError::InvalidRoot)
+        ));
+        assert!(matches!(
+            Values::from_serialize(&BTreeMap::from([("a", vec![vec![1]])])),
+            Err(SerializeError::Nested { name }) if name == "a"
+        ));
+    }
+}
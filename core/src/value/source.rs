@@ -0,0 +1,298 @@
+use std::{
+    borrow::Borrow,
+    collections::{
+        BTreeMap,
+        HashMap,
+    },
+    hash::{
+        BuildHasher,
+        Hash,
+    },
+    marker::PhantomData,
+};
+
+use crate::value::{
+    reference::ValueRef,
+    Value,
+    Values,
+};
+
+// =============================================================================
+// Source
+// =============================================================================
+
+// Traits
+
+/// The [`VariableSource`] trait is implemented by types which may be used as
+/// the source of values during expansion, looking up values by variable name.
+///
+/// Implementations are provided for [`Values`], for maps of names to
+/// [`Value`] ([`HashMap`], [`BTreeMap`], and `IndexMap` when the `indexmap`
+/// feature is enabled), and for closures (see [`SourceFn`]). It may also be
+/// implemented for your own types, allowing expansion without first copying
+/// data to a [`Values`] collection.
+///
+/// ```
+/// # use uri_template_system_core::{ Template, ValueRef, VariableSource };
+/// #
+/// struct Context {
+///     user_id: String,
+/// }
+///
+/// impl VariableSource for Context {
+///     fn get(&self, name: &str) -> Option<ValueRef<'_>> {
+///         match name {
+///             "user_id" => Some(ValueRef::item(&self.user_id)),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// let template = Template::parse("/users/{user_id}").unwrap();
+/// let context = Context {
+///     user_id: String::from("1"),
+/// };
+///
+/// assert_eq!("/users/1", template.expand(&context).unwrap());
+/// ```
+pub trait VariableSource {
+    /// Gets the value of the named variable as a [`ValueRef`], if present.
+    fn get(&self, name: &str) -> Option<ValueRef<'_>>;
+}
+
+// -----------------------------------------------------------------------------
+
+// Functions
+
+/// The [`SourceFn`] type adapts a closure (or other function) which looks up
+/// values by variable name to a [`VariableSource`].
+///
+/// [`VariableSource`] is not implemented for closures directly, as a closure
+/// returns values borrowed from the data it captures rather than from the
+/// closure itself, and a blanket implementation has no way to relate the two
+/// lifetimes. The [`SourceFn`] type carries the lifetime of the captured data,
+/// allowing values to be returned for as long as the source is borrowed.
+///
+/// ```
+/// # use uri_template_system_core::{ SourceFn, Template, ValueRef };
+/// #
+/// let id = String::from("1");
+/// let source = SourceFn::new(|name| match name {
+///     "id" => Some(ValueRef::item(&id)),
+///     _ => None,
+/// });
+///
+/// let template = Template::parse("/users/{id}").unwrap();
+///
+/// assert_eq!("/users/1", template.expand(&source).unwrap());
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct SourceFn<'v, F> {
+    f: F,
+    _values: PhantomData<&'v ()>,
+}
+
+impl<'v, F> SourceFn<'v, F>
+where
+    F: Fn(&str) -> Option<ValueRef<'v>>,
+{
+    /// Constructs a new [`SourceFn`] from the given function.
+    pub const fn new(f: F) -> Self {
+        Self {
+            f,
+            _values: PhantomData,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Layers
+
+/// The [`Layered`] type is a [`VariableSource`] which looks up values in a
+/// [`Values`] collection first, and then in a fallback source (see
+/// [`Values::with_fallback`]), borrowing both.
+///
+/// A value present in the collection hides any value in the fallback,
+/// including a value of [`Value::Undefined`], which may be used to remove a
+/// value from the fallback for the purposes of expansion.
+///
+/// ```
+/// # use uri_template_system_core::{ Template, Value, Values };
+/// #
+/// let base = Values::default()
+///     .add("tenant", Value::item("acme"))
+///     .add("debug", Value::item("1"));
+/// let overlay = Values::default().add("debug", Value::Undefined);
+///
+/// let template = Template::parse("/{tenant}{?debug}").unwrap();
+///
+/// assert_eq!(
+///     "/acme",
+///     template.expand(&overlay.with_fallback(&base)).unwrap()
+/// );
+/// ```
+#[derive(Debug)]
+pub struct Layered<'v, S>
+where
+    S: ?Sized,
+{
+    values: &'v Values,
+    fallback: &'v S,
+}
+
+impl<'v, S> Layered<'v, S>
+where
+    S: VariableSource + ?Sized,
+{
+    pub(crate) const fn new(values: &'v Values, fallback: &'v S) -> Self {
+        Self { values, fallback }
+    }
+}
+
+impl<S> Clone for Layered<'_, S>
+where
+    S: ?Sized,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> Copy for Layered<'_, S> where S: ?Sized {}
+
+// =============================================================================
+// Source - Implementations
+// =============================================================================
+
+// Function
+
+impl<'v, F> VariableSource for SourceFn<'v, F>
+where
+    F: Fn(&str) -> Option<ValueRef<'v>>,
+{
+    fn get(&self, name: &str) -> Option<ValueRef<'_>> {
+        (self.f)(name)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Layers
+
+impl<S> VariableSource for Layered<'_, S>
+where
+    S: VariableSource + ?Sized,
+{
+    fn get(&self, name: &str) -> Option<ValueRef<'_>> {
+        VariableSource::get(self.values, name).or_else(|| self.fallback.get(name))
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Maps
+
+impl<K> VariableSource for BTreeMap<K, Value>
+where
+    K: Borrow<str> + Ord,
+{
+    fn get(&self, name: &str) -> Option<ValueRef<'_>> {
+        Self::get(self, name).map(ValueRef::from)
+    }
+}
+
+impl<K, S> VariableSource for HashMap<K, Value, S>
+where
+    K: Borrow<str> + Eq + Hash,
+    S: BuildHasher,
+{
+    fn get(&self, name: &str) -> Option<ValueRef<'_>> {
+        Self::get(self, name).map(ValueRef::from)
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<K, S> VariableSource for indexmap::IndexMap<K, Value, S>
+where
+    K: Borrow<str> + Eq + Hash,
+    S: BuildHasher,
+{
+    fn get(&self, name: &str) -> Option<ValueRef<'_>> {
+        Self::get(self, name).map(ValueRef::from)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Values
+
+impl VariableSource for Values {
+    fn get(&self, name: &str) -> Option<ValueRef<'_>> {
+        Self::get(self, name).map(ValueRef::from)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{
+        BTreeMap,
+        HashMap,
+    };
+
+    use super::*;
+    use crate::Template;
+
+    #[test]
+    fn map_source_expansion() {
+        let template = Template::parse("{/a,b}").unwrap();
+        let entries = [("a", Value::item("x")), ("b", Value::list(["y", "z"]))];
+
+        let btree_map = BTreeMap::from(entries.clone());
+        let hash_map = HashMap::<_, _>::from_iter(entries);
+
+        assert_eq!("/x/y,z", template.expand(&btree_map).unwrap());
+        assert_eq!("/x/y,z", template.expand(&hash_map).unwrap());
+    }
+
+    #[cfg(feature = "indexmap")]
+    #[test]
+    fn index_map_source_expansion() {
+        let template = Template::parse("{/a,b}").unwrap();
+        let index_map = indexmap::IndexMap::<_, _>::from_iter([
+            ("a", Value::item("x")),
+            ("b", Value::list(["y", "z"])),
+        ]);
+
+        assert_eq!("/x/y,z", template.expand(&index_map).unwrap());
+    }
+
+    #[test]
+    fn layered_source_expansion() {
+        let template = Template::parse("{/a,b,c}").unwrap();
+        let base = BTreeMap::from([("a", Value::item("x")), ("b", Value::item("y"))]);
+        let overlay = Values::default()
+            .add("b", Value::item("z"))
+            .add("c", Value::Undefined);
+        let top = Values::default().add("c", Value::item("w"));
+        let layered = overlay.with_fallback(&base);
+
+        assert_eq!("/x/z", template.expand(&layered).unwrap());
+        assert_eq!(
+            "/x/z/w",
+            template.expand(&top.with_fallback(&layered)).unwrap()
+        );
+    }
+
+    #[test]
+    fn function_source_expansion() {
+        let template = Template::parse("{?a,b}").unwrap();
+        let source = SourceFn::new(|name| (name == "b").then(|| ValueRef::item("y")));
+
+        assert_eq!("?b=y", template.expand(&source).unwrap());
+    }
+}
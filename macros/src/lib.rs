@@ -0,0 +1,190 @@
+#![deny(clippy::all)]
+#![deny(clippy::complexity)]
+#![deny(clippy::nursery)]
+#![deny(clippy::pedantic)]
+#![deny(clippy::perf)]
+#![deny(clippy::style)]
+#![deny(clippy::suspicious)]
+#![deny(missing_docs)]
+#![doc = include_str!("../README.md")]
+
+mod template;
+mod values;
+
+use proc_macro::TokenStream;
+use syn::{
+    parse_macro_input,
+    DeriveInput,
+};
+
+// =============================================================================
+// Macros
+// =============================================================================
+
+/// Derives an implementation of `VariableSource` for a struct with named
+/// fields, such that the struct may be used directly as the source of values
+/// when expanding a template.
+///
+/// Each field is available as a variable with the same name as the field, and
+/// is converted to a value using `AsValueRef` (so strings are items, vecs of
+/// strings are lists, maps are associative arrays, and `None` is undefined).
+/// Field attributes may be used to change the behaviour for a field:
+///
+/// - `#[template(rename = "name")]` makes the field available as a variable
+///   with the given name
+/// - `#[template(skip)]` excludes the field
+/// - `#[template(flatten)]` makes the variables of the field (which must itself
+///   implement `VariableSource`) available, where not already given by another
+///   field
+/// - `#[template(list)]` converts the field using `ValueRef::list` (for any
+///   type which implements `Into<Items>` when borrowed)
+/// - `#[template(assoc)]` converts the field using
+///   `ValueRef::associative_array` (for any type which implements `Into<Pairs>`
+///   when borrowed)
+///
+/// ```
+/// # use uri_template_system::{ Template, TemplateValues };
+/// #
+/// #[derive(TemplateValues)]
+/// struct Paging {
+///     page: String,
+///     #[template(rename = "per_page")]
+///     size: Option<String>,
+/// }
+///
+/// #[derive(TemplateValues)]
+/// struct Search {
+///     q: String,
+///     #[template(list)]
+///     tags: Vec<String>,
+///     #[template(flatten)]
+///     paging: Paging,
+///     #[template(skip)]
+///     _internal: u64,
+/// }
+///
+/// let template = Template::parse("/search{?q,tags,page,per_page}").unwrap();
+/// let search = Search {
+///     q: String::from("rust"),
+///     tags: vec![String::from("a"), String::from("b")],
+///     paging: Paging {
+///         page: String::from("2"),
+///         size: None,
+///     },
+///     _internal: 0,
+/// };
+///
+/// assert_eq!(
+///     "/search?q=rust&tags=a,b&page=2",
+///     template.expand(&search).unwrap()
+/// );
+/// ```
+///
+/// A template may also be bound to the struct, using struct attributes:
+///
+/// - `#[template(uri = "...")]` checks the template at compile time, failing
+///   compilation if the template is invalid or references a variable which is
+///   not provided by a field, and generates `template()`, `expand(&self)`, and
+///   `try_expand(&self, &ExpandOptions)` functions for the struct
+/// - `#[template(deny_unused)]` additionally fails compilation if a field
+///   provides a variable which is not referenced by the template
+///
+/// The generated `template()` function parses the template on first use, and
+/// returns the cached `&'static Template<'static>` thereafter. The generated
+/// `expand(&self)` function expands with fixed options - prefix modifiers
+/// applied to composite values are ignored (`CompositePrefix::Ignore`) and
+/// undefined values are skipped (`Missing::Skip`) - so as the fields of a bound
+/// struct always provide the variables of the template, it does not fail, and
+/// returns a `String`. Where other options are required, the generated
+/// `try_expand(&self, &ExpandOptions)` function expands with the given options,
+/// returning a `Result`. Bound structs can not have flattened fields, as their
+/// variables can not be checked.
+///
+/// ```
+/// # use uri_template_system::TemplateValues;
+/// #
+/// #[derive(TemplateValues)]
+/// #[template(uri = "/users/{id}{?fields*}", deny_unused)]
+/// struct UserLink {
+///     id: String,
+///     fields: Vec<String>,
+/// }
+///
+/// let link = UserLink {
+///     id: String::from("1"),
+///     fields: vec![String::from("name")],
+/// };
+///
+/// assert_eq!("/users/1?fields=name", link.expand());
+/// ```
+///
+/// Templates referencing variables not provided by a field fail to compile:
+///
+/// ```compile_fail
+/// # use uri_template_system::TemplateValues;
+/// #
+/// #[derive(TemplateValues)]
+/// #[template(uri = "/users/{user_id}")]
+/// struct UserLink {
+///     id: String,
+/// }
+/// ```
+///
+/// Generated code refers to items of the `uri_template_system` crate by the
+/// path `::uri_template_system`. Where the crate is available under a different
+/// path (for example when renamed, or re-exported by another crate), the path
+/// may be given using the `#[template(crate = "path")]` struct attribute.
+#[proc_macro_derive(TemplateValues, attributes(template))]
+pub fn derive_template_values(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    values::derive(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Parses a string literal as a `Template` at compile time, failing
+/// compilation (with the error given by `Template::parse`) if the literal is
+/// not a valid URI Template.
+///
+/// The macro produces a `&'static Template<'static>` (borrowing from the
+/// literal) which is ready to use, without the need to handle a parse error
+/// which can not occur. The template is parsed when the macro is first
+/// evaluated, and cached for later evaluations of the same macro invocation, so
+/// there is no runtime cost beyond the first evaluation. As parsing happens at
+/// runtime, the macro can not be used in `const` or `static` initializers.
+///
+/// ```
+/// # use uri_template_system::{ template, Template, Value, Values };
+/// #
+/// let template: &'static Template<'static> = template!("/users/{id}{?fields*}");
+/// let values = Values::default()
+///     .add("id", Value::item("1"))
+///     .add("fields", Value::list(["name", "email"]));
+///
+/// assert_eq!(
+///     "/users/1?fields=name&fields=email",
+///     template.expand(&values).unwrap()
+/// );
+/// ```
+///
+/// Invalid templates fail to compile:
+///
+/// ```compile_fail
+/// # use uri_template_system::template;
+/// #
+/// let template = template!("/users/{id");
+/// ```
+///
+/// As with the derive, generated code refers to the `uri_template_system`
+/// crate by the path `::uri_template_system`, which may be overridden by
+/// following the literal with the path to use, as `template!("...", crate =
+/// "path")`.
+#[proc_macro]
+pub fn template(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as template::Input);
+
+    template::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,133 @@
+use std::ops::Range;
+
+use proc_macro2::{
+    Span,
+    TokenStream,
+};
+use quote::quote;
+use syn::{
+    parse::{
+        Parse,
+        ParseStream,
+    },
+    parse_quote,
+    Error,
+    LitStr,
+    Path,
+    Token,
+};
+use uri_template_system_core::{
+    ParseError,
+    Template,
+};
+
+// =============================================================================
+// Template
+// =============================================================================
+
+// Input
+
+/// The input to the `template!` macro, a string literal optionally followed by
+/// a path to the crate to use in generated code (`crate = "path"`).
+pub struct Input {
+    lit: LitStr,
+    krate: Path,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream<'_>) -> Result<Self, Error> {
+        let lit = input.parse()?;
+        let mut krate = None;
+
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            input.parse::<Token![crate]>()?;
+            input.parse::<Token![=]>()?;
+            krate = Some(input.parse::<LitStr>()?.parse()?);
+            input.parse::<Option<Token![,]>>()?;
+        }
+
+        Ok(Self {
+            lit,
+            krate: krate.unwrap_or_else(default_crate),
+        })
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Expand
+
+pub fn expand(input: &Input) -> Result<TokenStream, Error> {
+    parse(&input.lit, &input.lit.value())?;
+
+    Ok(cached(&input.krate, &input.lit))
+}
+
+/// The default path to the `uri-template-system` crate in generated code.
+pub fn default_crate() -> Path {
+    parse_quote!(::uri_template_system)
+}
+
+/// Parses the literal (given the value of the literal), returning an error
+/// spanning the position of the parse error on failure.
+pub fn parse<'t>(lit: &LitStr, raw: &'t str) -> Result<Template<'t>, Error> {
+    Template::parse(raw).map_err(|err| {
+        Error::new(
+            error_span(lit, &err),
+            format!("invalid URI template: {err}"),
+        )
+    })
+}
+
+/// Tokens which parse the literal at runtime on first evaluation, caching the
+/// template in a static (so that each use of the tokens has its own cache) and
+/// giving a `&'static Template<'static>`.
+pub fn cached(krate: &Path, lit: &LitStr) -> TokenStream {
+    let parse = tokens(krate, lit);
+
+    quote! {
+        {
+            static TEMPLATE: ::std::sync::OnceLock<#krate::Template<'static>> =
+                ::std::sync::OnceLock::new();
+
+            TEMPLATE.get_or_init(|| #parse)
+        }
+    }
+}
+
+/// Tokens which parse the literal at runtime, where the literal has already
+/// been validated (so parsing will not fail).
+fn tokens(krate: &Path, lit: &LitStr) -> TokenStream {
+    quote! {
+        match #krate::Template::parse(#lit) {
+            ::core::result::Result::Ok(template) => template,
+            ::core::result::Result::Err(_) => ::core::unreachable!(),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Spans
+
+// The span of the given range within the literal, where the compiler supports
+// spans within literals (currently only on nightly) and the literal contains no
+// escapes (so that positions within the value are also positions within the
+// source). Otherwise the span of the whole literal.
+
+pub fn literal_span(lit: &LitStr, range: Range<usize>) -> Span {
+    let raw = lit.value();
+    let token = lit.token();
+    let source = token.to_string();
+
+    source
+        .find('"')
+        .map(|quote| quote + 1)
+        .filter(|start| source.get(*start..*start + raw.len()) == Some(raw.as_str()))
+        .and_then(|start| token.subspan(start + range.start..start + range.end))
+        .unwrap_or_else(|| lit.span())
+}
+
+fn error_span(lit: &LitStr, err: &ParseError) -> Span {
+    literal_span(lit, err.span())
+}
@@ -0,0 +1,344 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    ext::IdentExt,
+    spanned::Spanned,
+    Data,
+    DeriveInput,
+    Error,
+    Field,
+    Fields,
+    LitStr,
+    Path,
+};
+
+use crate::template;
+
+// =============================================================================
+// Values
+// =============================================================================
+
+// Derive
+
+pub fn derive(input: &DeriveInput) -> Result<TokenStream, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(unsupported(input)),
+        },
+        _ => return Err(unsupported(input)),
+    };
+
+    let options = StructOptions::parse(input)?;
+    let krate = &options.krate;
+    let mut names = Vec::<LitStr>::new();
+    let mut arms = Vec::new();
+    let mut flattened = Vec::new();
+
+    for field in fields {
+        let ident = field.ident.as_ref().expect("named field");
+
+        match FieldOptions::parse(field)? {
+            FieldOptions::Skip => {}
+            FieldOptions::Flatten => flattened.push(quote! {
+                #krate::VariableSource::get(&self.#ident, name)
+            }),
+            FieldOptions::Value { rename, repr } => {
+                let name =
+                    rename.unwrap_or_else(|| LitStr::new(&ident.unraw().to_string(), ident.span()));
+
+                if names
+                    .iter()
+                    .any(|existing| existing.value() == name.value())
+                {
+                    return Err(Error::new(
+                        name.span(),
+                        format!("duplicate variable name \"{}\"", name.value()),
+                    ));
+                }
+
+                names.push(name.clone());
+
+                let value = match repr {
+                    Repr::AssociativeArray => quote! {
+                        #krate::ValueRef::associative_array(&self.#ident)
+                    },
+                    Repr::Default => quote! {
+                        #krate::AsValueRef::as_value_ref(&self.#ident)
+                    },
+                    Repr::List => quote! {
+                        #krate::ValueRef::list(&self.#ident)
+                    },
+                };
+
+                arms.push(quote! {
+                    #name => ::core::option::Option::Some(#value),
+                });
+            }
+        }
+    }
+
+    let fallback = flattened.split_first().map_or_else(
+        || quote! { ::core::option::Option::None },
+        |(first, rest)| quote! { #first #(.or_else(|| #rest))* },
+    );
+
+    let binding = match options.uri {
+        Some(uri) => bind(
+            input,
+            krate,
+            &uri,
+            options.deny_unused,
+            &names,
+            !flattened.is_empty(),
+        )?,
+        None => TokenStream::new(),
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #binding
+
+        #[automatically_derived]
+        impl #impl_generics #krate::VariableSource for #ident #ty_generics #where_clause {
+            fn get(&self, name: &str) -> ::core::option::Option<#krate::ValueRef<'_>> {
+                match name {
+                    #(#arms)*
+                    _ => #fallback,
+                }
+            }
+        }
+    })
+}
+
+// Binding
+
+// Where a template is bound to the struct (using the uri struct attribute), the
+// template is checked at compile time against the variables provided by the
+// fields, and typed template and expand functions are generated.
+
+fn bind(
+    input: &DeriveInput,
+    krate: &Path,
+    uri: &LitStr,
+    deny_unused: bool,
+    names: &[LitStr],
+    flattened: bool,
+) -> Result<TokenStream, Error> {
+    if flattened {
+        return Err(Error::new(
+            uri.span(),
+            "a bound template can not be checked against flattened fields",
+        ));
+    }
+
+    let raw = uri.value();
+    let template = template::parse(uri, &raw)?;
+    let variables = template
+        .unique_variables()
+        .map(|variable| (variable.name().name(), variable.span()))
+        .collect::<Vec<_>>();
+
+    let missing = variables
+        .iter()
+        .filter(|(name, _)| !names.iter().any(|existing| existing.value() == *name))
+        .map(|(name, span)| {
+            Error::new(
+                template::literal_span(uri, span.clone()),
+                format!(
+                    "template variable \"{name}\" is not provided by {}",
+                    input.ident
+                ),
+            )
+        });
+
+    let unused = names
+        .iter()
+        .filter(|name| {
+            deny_unused
+                && !variables
+                    .iter()
+                    .any(|(existing, _)| name.value() == *existing)
+        })
+        .map(|name| {
+            Error::new(
+                name.span(),
+                format!("variable \"{}\" is not used by the template", name.value()),
+            )
+        });
+
+    if let Some(err) = missing.chain(unused).reduce(|mut err, next| {
+        err.combine(next);
+        err
+    }) {
+        return Err(err);
+    }
+
+    let vis = &input.vis;
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let cached = template::cached(krate, uri);
+    let template_doc = format!(
+        "Gets the template bound to [`{ident}`] (`{raw}`), which is parsed on first use and \
+         cached."
+    );
+    let expand_doc = format!(
+        "Expands the template bound to [`{ident}`] (`{raw}`) using the values of the \
+         fields.\n\nThe expansion options are fixed: prefix modifiers applied to composite values \
+         are always ignored (`CompositePrefix::Ignore`), and undefined values are skipped \
+         (`Missing::Skip`). With these options expansion can not fail, as the fields provide \
+         every variable of the template (checked at compile time). Use `try_expand` for other \
+         options."
+    );
+    let try_expand_doc = format!(
+        "Expands the template bound to [`{ident}`] (`{raw}`) using the values of the fields and \
+         the given `ExpandOptions`.\n\n# Errors\n\nThis function will fail where the options make \
+         expansion fail for the values of the fields (see `Template::expand_with`)."
+    );
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #[doc = #template_doc]
+            #[must_use]
+            #vis fn template() -> &'static #krate::Template<'static> {
+                #cached
+            }
+
+            #[doc = #expand_doc]
+            #[must_use]
+            #vis fn expand(&self) -> ::std::string::String {
+                let options = #krate::ExpandOptions::default()
+                    .composite_prefix(#krate::CompositePrefix::Ignore);
+
+                match Self::template().expand_with(self, &options) {
+                    ::core::result::Result::Ok(expanded) => expanded,
+                    ::core::result::Result::Err(_) => {
+                        ::core::unreachable!("expansion of a bound template can not fail")
+                    }
+                }
+            }
+
+            #[doc = #try_expand_doc]
+            #vis fn try_expand(
+                &self,
+                options: &#krate::ExpandOptions,
+            ) -> ::core::result::Result<::std::string::String, #krate::ExpandError> {
+                Self::template().expand_with(self, options)
+            }
+        }
+    })
+}
+
+fn unsupported(input: &DeriveInput) -> Error {
+    Error::new(
+        input.ident.span(),
+        "TemplateValues can only be derived for structs with named fields",
+    )
+}
+
+// -----------------------------------------------------------------------------
+
+// Options
+
+struct StructOptions {
+    deny_unused: bool,
+    krate: Path,
+    uri: Option<LitStr>,
+}
+
+impl StructOptions {
+    fn parse(input: &DeriveInput) -> Result<Self, Error> {
+        let mut deny_unused = None;
+        let mut krate = None;
+        let mut uri = None;
+
+        for attr in input
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("template"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("crate") {
+                    krate = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                } else if meta.path.is_ident("deny_unused") {
+                    deny_unused = Some(meta.path.span());
+                } else if meta.path.is_ident("uri") {
+                    uri = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("unsupported template attribute"));
+                }
+
+                Ok(())
+            })?;
+        }
+
+        match (deny_unused, uri) {
+            (Some(span), None) => Err(Error::new(span, "deny_unused requires a uri")),
+            (deny_unused, uri) => Ok(Self {
+                deny_unused: deny_unused.is_some(),
+                krate: krate.unwrap_or_else(template::default_crate),
+                uri,
+            }),
+        }
+    }
+}
+
+enum FieldOptions {
+    Flatten,
+    Skip,
+    Value { rename: Option<LitStr>, repr: Repr },
+}
+
+enum Repr {
+    AssociativeArray,
+    Default,
+    List,
+}
+
+impl FieldOptions {
+    fn parse(field: &Field) -> Result<Self, Error> {
+        let mut flatten = false;
+        let mut skip = false;
+        let mut rename = None;
+        let mut repr = Repr::Default;
+
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("template"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("assoc") {
+                    repr = Repr::AssociativeArray;
+                } else if meta.path.is_ident("flatten") {
+                    flatten = true;
+                } else if meta.path.is_ident("list") {
+                    repr = Repr::List;
+                } else if meta.path.is_ident("rename") {
+                    rename = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("skip") {
+                    skip = true;
+                } else {
+                    return Err(meta.error("unsupported template attribute"));
+                }
+
+                Ok(())
+            })?;
+        }
+
+        let conflict = |message| Err(Error::new(field.span(), message));
+
+        match (skip, flatten, rename, repr) {
+            (true, false, None, Repr::Default) => Ok(Self::Skip),
+            (true, ..) => conflict("skip can not be combined with other template attributes"),
+            (false, true, None, Repr::Default) => Ok(Self::Flatten),
+            (false, true, ..) => {
+                conflict("flatten can not be combined with other template attributes")
+            }
+            (false, false, rename, repr) => Ok(Self::Value { rename, repr }),
+        }
+    }
+}
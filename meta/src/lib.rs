@@ -9,3 +9,8 @@
 #![doc = include_str!("../README.md")]
 
 pub use uri_template_system_core::*;
+#[cfg(feature = "macros")]
+pub use uri_template_system_macros::{
+    template,
+    TemplateValues,
+};
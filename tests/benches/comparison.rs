@@ -40,7 +40,11 @@ fn bench_set(c: &mut Criterion, name: &str, groups: Vec<Group>) {
             b.iter_batched_ref(
                 || setup(&group.cases),
                 |(input, output): &mut (Vec<String>, Vec<String>)| {
-                    output.extend(input.iter().map(|template| harness.test(template, &values)));
+                    output.extend(
+                        input
+                            .iter()
+                            .map(|template| harness.test(template, &values).unwrap()),
+                    );
                 },
                 BatchSize::SmallInput,
             )
@@ -57,7 +61,7 @@ fn bench_set(c: &mut Criterion, name: &str, groups: Vec<Group>) {
                         output.extend(
                             input
                                 .iter()
-                                .map(|template| harness.test(template, &group.variables)),
+                                .map(|template| harness.test(template, &group.variables).unwrap()),
                         );
                     },
                     BatchSize::SmallInput,
@@ -77,7 +81,7 @@ fn bench_set(c: &mut Criterion, name: &str, groups: Vec<Group>) {
                         output.extend(
                             input
                                 .iter()
-                                .map(|template| harness.test(template, &context)),
+                                .map(|template| harness.test(template, &context).unwrap()),
                         );
                     },
                     BatchSize::SmallInput,
@@ -89,7 +93,7 @@ fn bench_set(c: &mut Criterion, name: &str, groups: Vec<Group>) {
     g.finish();
 }
 
-fn setup(cases: &Vec<Case>) -> (Vec<String>, Vec<String>) {
+fn setup(cases: &[Case]) -> (Vec<String>, Vec<String>) {
     (
         cases.iter().map(|c| c.template.clone()).collect(),
         Vec::with_capacity(cases.len()),
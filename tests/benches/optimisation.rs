@@ -32,14 +32,14 @@ fn bench_set(c: &mut Criterion, name: &str, groups: Vec<Group>) {
         let harness = uri_template_system::Harness;
         let values = harness.prepare(group.variables.clone());
 
-        c.bench_function(&format!("{}: {}", name, &group.name), |b| {
+        c.bench_function(&format!("{}: {}", name, group.name), |b| {
             b.iter_batched_ref(
                 || setup(&group),
                 |(input, output): &mut (Vec<String>, Vec<String>)| {
                     output.extend(
                         input
                             .iter()
-                            .map(|template| harness.test(template, &values)),
+                            .map(|template| harness.test(template, &values).unwrap()),
                     );
                 },
                 BatchSize::SmallInput,
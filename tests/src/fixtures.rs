@@ -2,6 +2,7 @@ use std::sync::OnceLock;
 
 use indexmap::IndexMap;
 use serde::Deserialize;
+use uri_template_system_core::Value;
 
 // =============================================================================
 // Fixtures
@@ -9,17 +10,22 @@ use serde::Deserialize;
 
 #[must_use]
 pub fn examples() -> Vec<Group> {
-    data().0.clone()
+    data().examples.clone()
 }
 
 #[must_use]
 pub fn examples_by_section() -> Vec<Group> {
-    data().1.clone()
+    data().examples_by_section.clone()
 }
 
 #[must_use]
 pub fn extended_tests() -> Vec<Group> {
-    data().2.clone()
+    data().extended_tests.clone()
+}
+
+#[must_use]
+pub fn negative_tests() -> Vec<Group> {
+    data().negative_tests.clone()
 }
 
 // -----------------------------------------------------------------------------
@@ -49,6 +55,7 @@ pub enum Variable {
 
 #[derive(Clone, Debug)]
 pub enum Expansion {
+    Failure,
     Single(String),
     Multiple(Vec<String>),
 }
@@ -78,21 +85,28 @@ enum JSONVariable {
 enum JSONExpansion {
     String(String),
     List(Vec<String>),
+    Failure(bool),
 }
 
 // -----------------------------------------------------------------------------
 
 // Data
 
-static DATA: OnceLock<(Vec<Group>, Vec<Group>, Vec<Group>)> = OnceLock::new();
+struct Data {
+    examples: Vec<Group>,
+    examples_by_section: Vec<Group>,
+    extended_tests: Vec<Group>,
+    negative_tests: Vec<Group>,
+}
+
+static DATA: OnceLock<Data> = OnceLock::new();
 
-fn data() -> &'static (Vec<Group>, Vec<Group>, Vec<Group>) {
-    DATA.get_or_init(|| {
-        (
-            load(include_str!("../data/spec-examples.json")),
-            load(include_str!("../data/spec-examples-by-section.json")),
-            load(include_str!("../data/extended-tests.json")),
-        )
+fn data() -> &'static Data {
+    DATA.get_or_init(|| Data {
+        examples: load(include_str!("../data/spec-examples.json")),
+        examples_by_section: load(include_str!("../data/spec-examples-by-section.json")),
+        extended_tests: load(include_str!("../data/extended-tests.json")),
+        negative_tests: load(include_str!("../data/negative-tests.json")),
     })
 }
 
@@ -110,7 +124,7 @@ fn load(fixtures_json: &str) -> Vec<Group> {
                     JSONVariable::AssociativeArray(value) => (name, Variable::AssociativeArray(value.into_iter().collect())),
                     JSONVariable::Item(value) => (name, Variable::Item(value)),
                     JSONVariable::List(value) =>(name, Variable::List(value)),
-                    JSONVariable::Number(value) => (name, Variable::Item(value.to_string())),
+                    JSONVariable::Number(value) => (name, number(value)),
                     JSONVariable::Undefined => (name, Variable::Undefined),
                 })
                 .collect(),
@@ -120,6 +134,8 @@ fn load(fixtures_json: &str) -> Vec<Group> {
                 .map(|(template, expansion)| Case {
                     template,
                     expansion: match expansion {
+                        JSONExpansion::Failure(false) => Expansion::Failure,
+                        JSONExpansion::Failure(true) => panic!("unexpected expansion: true"),
                         JSONExpansion::List(expansion) => Expansion::Multiple(expansion),
                         JSONExpansion::String(expansion) => Expansion::Single(expansion),
                     },
@@ -128,3 +144,13 @@ fn load(fixtures_json: &str) -> Vec<Group> {
         })
         .collect()
 }
+
+// Numbers are converted as values would be by users of the library, so that
+// fixtures exercise the same formatting (see `Value::display`).
+
+fn number(value: f32) -> Variable {
+    match Value::from(value) {
+        Value::Item(value) => Variable::Item(value),
+        _ => unreachable!("numbers are converted to items"),
+    }
+}
@@ -4,7 +4,10 @@ pub mod iri_string;
 pub mod uri_template_next;
 pub mod uri_template_system;
 
-use std::fmt::Debug;
+use std::{
+    error::Error,
+    fmt::Debug,
+};
 
 use crate::fixtures::Variable;
 
@@ -21,5 +24,10 @@ where
     type Values;
 
     fn prepare(&self, variables: Vec<(String, Variable)>) -> Self::Values;
-    fn test(&self, template: &str, values: &Self::Values) -> String;
+    /// Parses and expands the template with the prepared values.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either parsing or expansion fails.
+    fn test(&self, template: &str, values: &Self::Values) -> Result<String, Box<dyn Error>>;
 }
@@ -1,3 +1,5 @@
+use std::error::Error;
+
 use iri_string::{
     spec::UriSpec,
     template::{
@@ -34,11 +36,9 @@ impl harnesses::Harness for Harness {
             })
     }
 
-    fn test(&self, template: &str, context: &SimpleContext) -> String {
-        UriTemplateStr::new(template)
-            .unwrap()
-            .expand::<UriSpec, _>(context)
-            .unwrap()
-            .to_string()
+    fn test(&self, template: &str, context: &SimpleContext) -> Result<String, Box<dyn Error>> {
+        Ok(UriTemplateStr::new(template)?
+            .expand::<UriSpec, _>(context)?
+            .to_string())
     }
 }
@@ -1,3 +1,5 @@
+use std::error::Error;
+
 use uritemplate::UriTemplate;
 
 use crate::{
@@ -14,8 +16,15 @@ impl harnesses::Harness for Harness {
         variables
     }
 
-    fn test(&self, template: &str, variables: &Vec<(String, Variable)>) -> String {
-        variables
+    // URITemplate Next has no failure mode - invalid templates are expanded on a
+    // best-effort basis, and so this harness never fails.
+
+    fn test(
+        &self,
+        template: &str,
+        variables: &Vec<(String, Variable)>,
+    ) -> Result<String, Box<dyn Error>> {
+        Ok(variables
             .iter()
             .fold(UriTemplate::new(template), |mut template, (n, v)| {
                 match v {
@@ -27,6 +36,6 @@ impl harnesses::Harness for Harness {
 
                 template
             })
-            .build()
+            .build())
     }
 }
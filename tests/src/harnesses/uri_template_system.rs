@@ -1,4 +1,8 @@
+use std::error::Error;
+
 use uri_template_system_core::{
+    CompositePrefix,
+    ExpandOptions,
     Template,
     Value,
     Values,
@@ -22,7 +26,9 @@ impl super::Harness for Harness {
             })
     }
 
-    fn test(&self, template: &str, values: &Self::Values) -> String {
-        Template::parse(template).unwrap().expand(values).unwrap()
+    fn test(&self, template: &str, values: &Self::Values) -> Result<String, Box<dyn Error>> {
+        let options = ExpandOptions::default().composite_prefix(CompositePrefix::Error);
+
+        Ok(Template::parse(template)?.expand_with(values, &options)?)
     }
 }
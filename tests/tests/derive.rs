@@ -0,0 +1,198 @@
+use uri_template_system::{
+    ExpandError,
+    ExpandOptions,
+    Missing,
+    Template,
+    TemplateValues,
+    Value,
+    VariableSource,
+};
+use uri_template_system_tests::fixtures::{
+    self,
+    Expansion,
+};
+
+// =============================================================================
+// Derive
+// =============================================================================
+
+// Types
+
+// The variables of the Level 4 examples, as a typed struct, with the fields
+// split across a flattened struct and named (or represented) differently to the
+// variables where required, to exercise each of the field attributes.
+
+#[derive(TemplateValues)]
+struct Level4 {
+    var: &'static str,
+    #[template(rename = "hello")]
+    greeting: String,
+    #[template(list)]
+    list: Vec<String>,
+    #[template(assoc)]
+    keys: [(&'static str, &'static str); 3],
+    #[template(flatten)]
+    other: Other,
+    #[template(skip)]
+    _skipped: usize,
+}
+
+#[derive(TemplateValues)]
+struct Other {
+    path: Option<String>,
+    var: Value,
+    r#undef: Option<String>,
+}
+
+fn level_4() -> Level4 {
+    Level4 {
+        var: "value",
+        greeting: String::from("Hello World!"),
+        list: vec![
+            String::from("red"),
+            String::from("green"),
+            String::from("blue"),
+        ],
+        keys: [("semi", ";"), ("dot", "."), ("comma", ",")],
+        other: Other {
+            path: Some(String::from("/foo/bar")),
+            var: Value::item("shadowed"),
+            r#undef: None,
+        },
+        _skipped: 0,
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Tests
+
+// Derived values are tested against the official Level 4 examples - expansion
+// using the derived source should produce one of the expected expansions.
+
+#[test]
+fn uri_template_system() {
+    let values = level_4();
+    let group = fixtures::examples()
+        .into_iter()
+        .find(|group| group.name == "Level 4 Examples")
+        .unwrap();
+
+    for (i, case) in group.cases.iter().enumerate() {
+        let actual = Template::parse(&case.template)
+            .unwrap()
+            .expand(&values)
+            .unwrap();
+
+        match &case.expansion {
+            Expansion::Failure => panic!("Level 4 Examples - {i}: Unexpected failure case."),
+            Expansion::Single(expected) => assert!(
+                expected.eq(&actual),
+                "Level 4 Examples - {i}: Actual expansion \"{actual}\" not equal to expected \
+                 expansion \"{expected}\"."
+            ),
+            Expansion::Multiple(expected) => assert!(
+                expected.contains(&actual),
+                "Level 4 Examples - {i}: Actual expansion \"{actual}\" not in expected expansions \
+                 {expected:?}."
+            ),
+        }
+    }
+}
+
+#[test]
+fn field_attributes() {
+    let values = level_4();
+
+    assert!(values.get("greeting").is_none());
+    assert!(values.get("_skipped").is_none());
+    assert!(values.get("undef").is_some());
+    assert_eq!(
+        "value",
+        Template::parse("{var}").unwrap().expand(&values).unwrap()
+    );
+}
+
+// -----------------------------------------------------------------------------
+
+// Binding
+
+#[derive(TemplateValues)]
+#[template(uri = "{/list*,path:4}{?keys*}{&var}", deny_unused)]
+struct Bound {
+    list: Vec<&'static str>,
+    path: String,
+    keys: Value,
+    #[template(rename = "var")]
+    value: Option<String>,
+    #[template(skip)]
+    _skipped: usize,
+}
+
+#[test]
+fn bound_expansion() {
+    let bound = Bound {
+        list: vec!["red", "green"],
+        path: String::from("/foo/bar"),
+        keys: Value::associative_array([("semi", ";")]),
+        value: None,
+        _skipped: 0,
+    };
+
+    assert_eq!(
+        &Template::parse("{/list*,path:4}{?keys*}{&var}").unwrap(),
+        Bound::template()
+    );
+    assert!(std::ptr::eq(Bound::template(), Bound::template()));
+    assert_eq!("/red/green/%2Ffoo?semi=%3B", bound.expand());
+}
+
+#[test]
+fn bound_fallible_expansion() {
+    let bound = Bound {
+        list: vec!["red", "green"],
+        path: String::from("/foo/bar"),
+        keys: Value::associative_array([("semi", ";")]),
+        value: None,
+        _skipped: 0,
+    };
+
+    assert_eq!(
+        "/red/green/%2Ffoo?semi=%3B",
+        bound.try_expand(&ExpandOptions::default()).unwrap()
+    );
+    assert!(matches!(
+        bound.try_expand(&ExpandOptions::default().missing(Missing::Error)),
+        Err(ExpandError::Missing { .. })
+    ));
+}
+
+// -----------------------------------------------------------------------------
+
+// Paths
+
+// The path to the crate used by generated code may be overridden, so that a
+// struct may derive values where the crate is only available under another
+// path (here the core crate, which provides everything generated code uses).
+
+#[derive(TemplateValues)]
+#[template(crate = "uri_template_system_core", uri = "/users/{id}")]
+struct Core {
+    id: String,
+}
+
+#[test]
+fn crate_path() {
+    let core = Core {
+        id: String::from("1"),
+    };
+
+    assert_eq!(
+        "/users/1",
+        uri_template_system_core::Template::parse("/users/{id}")
+            .unwrap()
+            .expand(&core)
+            .unwrap()
+    );
+    assert_eq!("/users/1", core.expand());
+}
@@ -0,0 +1,59 @@
+use uri_template_system_core::Template;
+use uri_template_system_tests::fixtures::{
+    self,
+    Group,
+};
+
+// =============================================================================
+// Display
+// =============================================================================
+
+// Tests
+
+// Display is tested as the inverse of parsing - any official template should
+// display as the original template text, and parse again to an equal template.
+// The normalized form should differ only in case, and should be stable.
+
+#[test]
+fn uri_template_system() {
+    test_set("Examples", fixtures::examples());
+    test_set("Examples By Section", fixtures::examples_by_section());
+    test_set("Extended Tests", fixtures::extended_tests());
+}
+
+fn test_set(name: &str, groups: Vec<Group>) {
+    for group in groups {
+        let name = format!("{name}: {}", group.name);
+
+        for (i, case) in group.cases.iter().enumerate() {
+            let template = Template::parse(&case.template).unwrap();
+            let displayed = template.to_string();
+
+            assert!(
+                case.template.eq(&displayed),
+                "{name} - {i}: Display \"{displayed}\" not equal to template \"{}\"",
+                case.template
+            );
+
+            assert_eq!(
+                template,
+                Template::parse(&displayed).unwrap(),
+                "{name} - {i}: Displayed template did not parse to an equal template"
+            );
+
+            let normalized = format!("{template:#}");
+
+            assert!(
+                case.template.eq_ignore_ascii_case(&normalized),
+                "{name} - {i}: Normalized \"{normalized}\" not equivalent to template \"{}\"",
+                case.template
+            );
+
+            assert_eq!(
+                normalized,
+                format!("{:#}", Template::parse(&normalized).unwrap()),
+                "{name} - {i}: Normalized template is not stable"
+            );
+        }
+    }
+}
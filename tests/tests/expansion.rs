@@ -19,10 +19,15 @@ use uri_template_system_tests::{
 // Testcases for URI Template processing are generated from the "official" test
 // cases published at https://github.com/uri-templates/uritemplate-test, and
 // included as a submodule in this repository (./official).
+//
+// The negative tests (where either parsing or expansion is expected to fail)
+// are run for every harness which is able to fail - URITemplate Next has no
+// failure mode, and so is only tested against the positive test cases.
 
 #[test]
 fn uri_template_system() {
     test_sets(harnesses::uri_template_system::Harness);
+    test_negative_set(harnesses::uri_template_system::Harness);
 }
 
 #[cfg(feature = "uritemplate-next")]
@@ -35,6 +40,7 @@ fn uri_template_next() {
 #[test]
 fn iri_string() {
     test_sets(harnesses::iri_string::Harness);
+    test_negative_set(harnesses::iri_string::Harness);
 }
 
 #[rustfmt::skip]
@@ -44,6 +50,10 @@ fn test_sets(testable: impl Harness) {
     test_set("Extended Tests", fixtures::extended_tests(), &testable);
 }
 
+fn test_negative_set(testable: impl Harness) {
+    test_set("Negative Tests", fixtures::negative_tests(), &testable);
+}
+
 fn test_set(name: &str, groups: Vec<Group>, harness: &impl Harness) {
     for group in groups {
         let name = format!("{name}: {}", group.name);
@@ -52,17 +62,25 @@ fn test_set(name: &str, groups: Vec<Group>, harness: &impl Harness) {
         for (i, case) in group.cases.iter().enumerate() {
             let expansion = &case.expansion;
             let template = &case.template;
-            let actual = harness.test(template, &values);
 
-            match expansion {
-                Expansion::Multiple(expected) => {
+            match (expansion, harness.test(template, &values)) {
+                (Expansion::Failure, Err(_)) => {}
+                (Expansion::Failure, Ok(actual)) => panic!(
+                    "{name} - {i}: Actual expansion \"{actual}\" produced where failure was \
+                     expected.\nTemplate: \"{template}\"\nValues: {values:#?}"
+                ),
+                (_, Err(err)) => panic!(
+                    "{name} - {i}: Expansion failed with error \"{err}\".\nTemplate: \
+                     \"{template}\"\nValues: {values:#?}"
+                ),
+                (Expansion::Multiple(expected), Ok(actual)) => {
                     assert!(
                         expected.contains(&actual),
                         "{name} - {i}: Actual expansion \"{actual}\" not found in expected \
                          expansions {expected:#?}.\nTemplate: \"{template}\"\nValues: {values:#?}"
                     )
                 }
-                Expansion::Single(expected) => {
+                (Expansion::Single(expected), Ok(actual)) => {
                     assert!(
                         expected.eq(&actual),
                         "{name} - {i}: Actual expansion \"{actual}\" not equal to expected \
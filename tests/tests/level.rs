@@ -0,0 +1,55 @@
+use uri_template_system_core::{
+    Level,
+    ParseOptions,
+    Template,
+};
+use uri_template_system_tests::fixtures;
+
+// =============================================================================
+// Level
+// =============================================================================
+
+// Tests
+
+// Levels are tested against the official examples, which are grouped by level -
+// every template in a group should be of the level of the group or lower (and
+// so parse when restricted to that level), and every group should contain at
+// least one template of the level of the group.
+
+#[test]
+fn uri_template_system() {
+    let levels = [
+        ("Level 1 Examples", Level::Level1),
+        ("Level 2 Examples", Level::Level2),
+        ("Level 3 Examples", Level::Level3),
+        ("Level 4 Examples", Level::Level4),
+    ];
+
+    for group in fixtures::examples() {
+        let (name, level) = levels.iter().find(|(name, _)| *name == group.name).unwrap();
+
+        let options = ParseOptions::default().max_level(*level);
+        let mut max = Level::Level1;
+
+        for (i, case) in group.cases.iter().enumerate() {
+            let template = Template::parse(&case.template).unwrap();
+
+            assert!(
+                template.level() <= *level,
+                "{name} - {i}: Template \"{}\" has level {}",
+                case.template,
+                template.level()
+            );
+
+            assert!(
+                Template::parse_with(&case.template, &options).is_ok(),
+                "{name} - {i}: Template \"{}\" not parsed with maximum level {level}",
+                case.template
+            );
+
+            max = max.max(template.level());
+        }
+
+        assert_eq!(*level, max, "{name}: No template of level {level}");
+    }
+}
@@ -0,0 +1,54 @@
+use uri_template_system_core::Template;
+use uri_template_system_tests::fixtures::{
+    self,
+    Expansion,
+    Group,
+};
+
+// =============================================================================
+// Matching
+// =============================================================================
+
+// Tests
+
+// Matching is tested as the inverse of expansion - any official expansion
+// should match the template from which it was produced, and the values bound
+// by matching should expand to the same expansion again.
+
+#[test]
+fn uri_template_system() {
+    test_set("Examples", fixtures::examples());
+    test_set("Examples By Section", fixtures::examples_by_section());
+    test_set("Extended Tests", fixtures::extended_tests());
+}
+
+fn test_set(name: &str, groups: Vec<Group>) {
+    for group in groups {
+        let name = format!("{name}: {}", group.name);
+
+        for (i, case) in group.cases.iter().enumerate() {
+            let template = Template::parse(&case.template).unwrap();
+            let expected = match &case.expansion {
+                Expansion::Failure => continue,
+                Expansion::Multiple(expected) => expected.first().unwrap(),
+                Expansion::Single(expected) => expected,
+            };
+
+            let values = template.match_uri(expected).unwrap_or_else(|| {
+                panic!(
+                    "{name} - {i}: Expansion \"{expected}\" did not match.\nTemplate: \"{}\"",
+                    case.template
+                )
+            });
+
+            let actual = template.expand(&values).unwrap();
+
+            assert!(
+                expected.eq(&actual),
+                "{name} - {i}: Re-expansion \"{actual}\" not equal to matched expansion \
+                 \"{expected}\".\nTemplate: \"{}\"\nValues: {values:#?}",
+                case.template
+            );
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use uri_template_system_core::{
+    ExpandError,
+    Template,
+};
+use uri_template_system_tests::{
+    fixtures::{
+        self,
+        Group,
+    },
+    harnesses::{
+        uri_template_system::Harness,
+        Harness as _,
+    },
+};
+
+// =============================================================================
+// Partial
+// =============================================================================
+
+// Tests
+
+// Partial expansion is tested against full expansion - for any official case,
+// expanding partially with alternate variables and then expanding the result
+// with the remaining variables should be equivalent to expanding the original
+// template with all variables (where the partial expansion is representable).
+
+#[test]
+fn uri_template_system() {
+    test_set("Examples", fixtures::examples());
+    test_set("Examples By Section", fixtures::examples_by_section());
+    test_set("Extended Tests", fixtures::extended_tests());
+}
+
+fn test_set(name: &str, groups: Vec<Group>) {
+    for group in groups {
+        let name = format!("{name}: {}", group.name);
+        let (first, second): (Vec<_>, Vec<_>) = group
+            .variables
+            .iter()
+            .cloned()
+            .enumerate()
+            .partition(|(i, _)| i % 2 == 0);
+
+        let all = Harness.prepare(group.variables);
+        let first = Harness.prepare(first.into_iter().map(|(_, v)| v).collect());
+        let second = Harness.prepare(second.into_iter().map(|(_, v)| v).collect());
+
+        for (i, case) in group.cases.iter().enumerate() {
+            let template = Template::parse(&case.template).unwrap();
+            let expected = template.expand(&all).unwrap();
+
+            match template.expand_partial(&first) {
+                Ok(partial) => {
+                    let actual = partial.expand(&second).unwrap();
+
+                    assert!(
+                        expected.eq(&actual),
+                        "{name} - {i}: Staged expansion \"{actual}\" not equal to full expansion \
+                         \"{expected}\".\nTemplate: \"{}\"\nPartial: \"{partial}\"",
+                        case.template
+                    );
+                }
+                Err(ExpandError::Unrepresentable { .. }) => {}
+                Err(err) => panic!("{name} - {i}: Unexpected error: {err}"),
+            }
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use indexmap::IndexMap;
+use serde::Deserialize;
+use uri_template_system_core::{
+    Template,
+    Values,
+};
+use uri_template_system_tests::{
+    fixtures::{
+        self,
+        Group,
+    },
+    harnesses::{
+        uri_template_system::Harness,
+        Harness as _,
+    },
+};
+
+// =============================================================================
+// Serialization
+// =============================================================================
+
+// Tests
+
+// Serialization is tested against the fixtures themselves - the variables of
+// each official group should deserialize (from the fixture JSON) to the same
+// values as those prepared by the harness, and both templates and values should
+// survive a round trip through JSON unchanged.
+
+#[derive(Deserialize)]
+struct JSONGroup {
+    variables: Values,
+}
+
+#[test]
+fn uri_template_system() {
+    test_set(
+        "Examples",
+        fixtures::examples(),
+        include_str!("../data/spec-examples.json"),
+    );
+    test_set(
+        "Examples By Section",
+        fixtures::examples_by_section(),
+        include_str!("../data/spec-examples-by-section.json"),
+    );
+    test_set(
+        "Extended Tests",
+        fixtures::extended_tests(),
+        include_str!("../data/extended-tests.json"),
+    );
+}
+
+fn test_set(name: &str, groups: Vec<Group>, json: &str) {
+    let json_groups = serde_json::from_str::<IndexMap<String, JSONGroup>>(json).unwrap();
+
+    for (group, (_, json_group)) in groups.into_iter().zip(json_groups) {
+        let name = format!("{name}: {}", group.name);
+        let values = Harness.prepare(group.variables);
+
+        assert_eq!(
+            values, json_group.variables,
+            "{name}: Deserialized values not equal to prepared values"
+        );
+
+        let serialized = serde_json::to_string(&values).unwrap();
+
+        assert_eq!(
+            values,
+            serde_json::from_str::<Values>(&serialized).unwrap(),
+            "{name}: Values not equal after round trip"
+        );
+
+        for (i, case) in group.cases.iter().enumerate() {
+            let template = Template::parse(&case.template).unwrap();
+            let serialized = serde_json::to_string(&template).unwrap();
+
+            assert_eq!(
+                template,
+                serde_json::from_str::<Template>(&serialized).unwrap(),
+                "{name} - {i}: Template not equal after round trip"
+            );
+        }
+    }
+}
+
+#[test]
+fn invalid_template_deserialization() {
+    assert!(serde_json::from_str::<Template>("\"/users/{id\"").is_err());
+}
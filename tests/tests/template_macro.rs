@@ -0,0 +1,69 @@
+use uri_template_system::{
+    template,
+    Template,
+};
+
+// =============================================================================
+// Template Macro
+// =============================================================================
+
+// Tests
+
+// Templates produced by the macro (validated at compile time) should be equal
+// to those produced by parsing at runtime. Literals are taken from the official
+// examples, covering each operator and modifier.
+
+#[test]
+fn uri_template_system() {
+    let cases: [(&Template<'static>, &str); 8] = [
+        (template!("{var}"), "{var}"),
+        (template!("{+path:6}/here"), "{+path:6}/here"),
+        (template!("{#keys*}"), "{#keys*}"),
+        (template!("X{.list}"), "X{.list}"),
+        (template!("{/list*,path:4}"), "{/list*,path:4}"),
+        (template!("{;keys*}"), "{;keys*}"),
+        (template!("{?var:3}"), "{?var:3}"),
+        (template!("?fixed=yes{&keys*}"), "?fixed=yes{&keys*}"),
+    ];
+
+    for (i, (template, raw)) in cases.into_iter().enumerate() {
+        assert_eq!(
+            &Template::parse(raw).unwrap(),
+            template,
+            "{i}: Macro template not equal to parsed template \"{raw}\""
+        );
+    }
+}
+
+#[test]
+fn raw_and_escaped_literals() {
+    assert_eq!(
+        &Template::parse("/a%20b/{c}").unwrap(),
+        template!(r"/a%20b/{c}")
+    );
+    assert_eq!(
+        &Template::parse("/\u{e9}/{c}").unwrap(),
+        template!("/\u{e9}/{c}")
+    );
+}
+
+#[test]
+fn cached_evaluation() {
+    fn users() -> &'static Template<'static> {
+        template!("/users/{id}")
+    }
+
+    assert!(std::ptr::eq(users(), users()));
+    assert!(!std::ptr::eq(users(), template!("/users/{id}")));
+}
+
+// The path to the crate used by generated code may be overridden, as for the
+// derive (here the core crate, which provides the template type).
+
+#[test]
+fn crate_path() {
+    assert_eq!(
+        &Template::parse("/users/{id}").unwrap(),
+        template!("/users/{id}", crate = "uri_template_system_core")
+    );
+}